@@ -1,11 +1,17 @@
 use super::Result;
 use crate::accounts::tokens::TokenOptions;
-use crate::{APIError, Address, BlockNumber, Client, Tag, TransactionHash, TypeExtensions, ACTION, ADDRESS, MODULE, TAG};
+use crate::convert::TokenAmount;
+use crate::{
+    APIError, Address, Amount, BlockId, BlockNumber, Client, DecimalU256, Tag, TransactionHash, TypeExtensions, ACTION, ADDRESS, MODULE,
+    TAG, U256,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
-use serde_with::{serde_as, DisplayFromStr, TimestampSecondsWithFrac};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampSecondsWithFrac};
+use std::future::Future;
 use tokens::{ERC20TokenTransfer, ERC721TokenTransfer};
 use transactions::{InternalTransaction, Transaction, TransactionOptions};
 
@@ -22,10 +28,21 @@ const ERC721_TOKEN_TRANSFERS: &str = "tokennfttx";
 const INTERNAL_TRANSACTIONS: &str = "txlistinternal";
 const OFFSET: &str = "offset";
 const PAGE: &str = "page";
+const PAGE_SIZE: u16 = 1000;
+/// The most records the API returns for a query, as the page number times the page size may not exceed it
+const RESULT_WINDOW: u64 = 10_000;
 const SORT: &str = "sort";
 const START_BLOCK: &str = "startblock";
 const TRANSACTIONS: &str = "txlist";
 
+/// A stream of the records of a list endpoint, fetched a page at a time.
+#[cfg(not(target_arch = "wasm32"))]
+pub type PageStream<'a, T> = futures::stream::BoxStream<'a, Result<T>>;
+
+/// A stream of the records of a list endpoint, fetched a page at a time.
+#[cfg(target_arch = "wasm32")]
+pub type PageStream<'a, T> = futures::stream::LocalBoxStream<'a, Result<T>>;
+
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait Accounts {
@@ -34,8 +51,12 @@ pub trait Accounts {
     /// # Arguments
     ///
     /// * 'address' - An address
-    /// * 'tag' - The pre-defined block parameter, which defaults to latest if not provided.
-    async fn balance(&self, address: &Address, tag: Option<Tag>) -> Result<u128>;
+    /// * 'block' - The block to get the balance at, which defaults to latest if not provided.
+    ///
+    /// **Note:** The balance at a block number is returned by the historical balance endpoint, which requires an API
+    /// PRO plan. The balances at the safe and finalized blocks are returned by the proxy endpoint, which does not,
+    /// while blocks by hash are not supported, as the API cannot look blocks up by hash.
+    async fn balance(&self, address: &Address, block: Option<BlockId>) -> Result<U256>;
 
     /// Returns the balances for multiple given addresses (max 20).
     ///
@@ -62,6 +83,18 @@ pub trait Accounts {
     /// * 'options' - Additional options.
     async fn transactions_with_options(&self, address: &Address, options: TransactionOptions) -> Result<Vec<Transaction>>;
 
+    /// Returns a stream of the (normal) transactions for a given address, which fetches each page as it is reached and
+    /// ends after the last page, which is shorter than the others. An error ends the stream after being returned.
+    ///
+    /// Beyond the 10,000 records the API returns for a query, the stream continues with a new query from the block of
+    /// the last transaction returned.
+    ///
+    /// # Arguments
+    ///
+    /// * 'address' - An address.
+    /// * 'options' - Additional options, whose page (if any) is the first page fetched and its size.
+    fn transactions_stream<'a>(&'a self, address: &'a Address, options: TransactionOptions) -> PageStream<'a, Transaction>;
+
     /// Returns the internal transactions for a given address (max 10,000).
     ///
     /// # Arguments
@@ -90,13 +123,41 @@ pub trait Accounts {
     /// **Note:** This API endpoint returns a maximum of 10,000 records only.
     async fn internal_transactions_with_options(&self, address: &Address, options: TransactionOptions) -> Result<Vec<InternalTransaction>>;
 
-    /// Returns the current balance of an ERC-20 token of an address.
+    /// Returns a stream of the internal transactions for a given address, which fetches each page as it is reached and
+    /// ends after the last page, which is shorter than the others. An error ends the stream after being returned.
+    ///
+    /// Beyond the 10,000 records the API returns for a query, the stream continues with a new query from the block of
+    /// the last transaction returned.
+    ///
+    /// # Arguments
+    ///
+    /// * 'address' - An address
+    /// * 'options' - Additional options, whose page (if any) is the first page fetched and its size.
+    fn internal_transactions_stream<'a>(&'a self, address: &'a Address, options: TransactionOptions)
+        -> PageStream<'a, InternalTransaction>;
+
+    /// Returns the current balance of an ERC-20 token of an address, in the smallest unit of the token. See
+    /// [`erc20_token_amount`](Accounts::erc20_token_amount) for the balance as a [`TokenAmount`].
+    ///
+    /// # Arguments
+    ///
+    /// * 'address' - An address
+    /// * 'contract_address' - A contract address
+    async fn erc20_token_balance(&self, address: &Address, contract_address: &Address) -> Result<U256>;
+
+    /// Returns the current balance of an ERC-20 token of an address, with the decimals and symbol of the token.
+    ///
+    /// The balance endpoint returns only the raw amount, and the decimals and symbol of a token are only available from
+    /// the API Pro `tokeninfo` endpoint or by calling the token contract, so they are given by the caller rather than
+    /// fetched, such as from the `token_decimal` and `token_symbol` of a transfer of the token.
     ///
     /// # Arguments
     ///
     /// * 'address' - An address
     /// * 'contract_address' - A contract address
-    async fn erc20_token_balance(&self, address: &Address, contract_address: &Address) -> Result<u128>;
+    /// * 'decimals' - The number of decimals of the token
+    /// * 'symbol' - The symbol of the token
+    async fn erc20_token_amount(&self, address: &Address, contract_address: &Address, decimals: u8, symbol: &str) -> Result<TokenAmount>;
 
     /// Returns the ERC20 token transfers for a given address and contract address.
     ///
@@ -127,6 +188,18 @@ pub trait Accounts {
     /// * 'options' - The token request options.
     async fn erc20_token_transfers_with_options<'a>(&self, options: TokenOptions<'a>) -> Result<Vec<ERC20TokenTransfer>>;
 
+    /// Returns a stream of the ERC20 token transfers based on the supplied options, which fetches each page as it is
+    /// reached and ends after the last page, which is shorter than the others. An error ends the stream after being
+    /// returned.
+    ///
+    /// Beyond the 10,000 records the API returns for a query, the stream continues with a new query from the block of
+    /// the last transfer returned.
+    ///
+    /// # Arguments
+    ///
+    /// * 'options' - The token request options, whose page (if any) is the first page fetched and its size.
+    fn erc20_token_transfers_stream<'a>(&'a self, options: TokenOptions<'a>) -> PageStream<'a, ERC20TokenTransfer>;
+
     /// Returns the ERC721 token transfers for a given address and contract address.
     ///
     /// # Arguments
@@ -156,25 +229,67 @@ pub trait Accounts {
     /// * 'options' - The token request options.
     async fn erc721_token_transfers_with_options<'a>(&self, options: TokenOptions<'a>) -> Result<Vec<ERC721TokenTransfer>>;
 
+    /// Returns a stream of the ERC721 token transfers based on the supplied options, which fetches each page as it is
+    /// reached and ends after the last page, which is shorter than the others. An error ends the stream after being
+    /// returned.
+    ///
+    /// Beyond the 10,000 records the API returns for a query, the stream continues with a new query from the block of
+    /// the last transfer returned.
+    ///
+    /// # Arguments
+    ///
+    /// * 'options' - The token request options, whose page (if any) is the first page fetched and its size.
+    fn erc721_token_transfers_stream<'a>(&'a self, options: TokenOptions<'a>) -> PageStream<'a, ERC721TokenTransfer>;
+
     /// Returns a list of blocks mined by an address.
     ///
     /// # Arguments
     ///
     /// * 'address' - An address
     async fn blocks_mined(&self, address: &Address, block_type: BlockType, page: Page) -> Result<Vec<Block>>;
+
+    /// Returns a stream of the blocks mined by an address, which fetches each page as it is reached and ends after the
+    /// last page, which is shorter than the others. An error ends the stream after being returned.
+    ///
+    /// **Note:** The API returns at most 10,000 blocks, as it cannot be queried from a block, so the stream ends once
+    /// they have been returned.
+    ///
+    /// # Arguments
+    ///
+    /// * 'address' - An address
+    /// * 'block_type' - The type of blocks
+    /// * 'page' - The first page fetched and its size
+    fn blocks_mined_stream<'a>(&'a self, address: &'a Address, block_type: BlockType, page: Page) -> PageStream<'a, Block>;
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Accounts for Client {
-    async fn balance(&self, address: &Address, tag: Option<Tag>) -> Result<u128> {
-        let parameters = &[
-            (MODULE, ACCOUNT),
-            (ACTION, "balance"),
-            (ADDRESS, &TypeExtensions::format(address)),
-            (TAG, tag.or(Some(Tag::Latest)).unwrap().to_string()),
-        ];
-        self.get::<String>(parameters).await.map(|v| v.parse::<u128>().unwrap_or(0))
+    async fn balance(&self, address: &Address, block: Option<BlockId>) -> Result<U256> {
+        let balance = match block.unwrap_or(BlockId::Latest) {
+            block @ BlockId::Hash(_) => return Err(APIError::UnsupportedBlock { block }),
+            // The balance endpoint does not accept the safe and finalized tags, unlike the proxy
+            block @ (BlockId::Safe | BlockId::Finalized) => return self.balance_at(address, block).await,
+            BlockId::Number(number) => {
+                let parameters = &[
+                    (MODULE, ACCOUNT),
+                    (ACTION, "balancehistory"),
+                    (ADDRESS, &TypeExtensions::format(address)),
+                    ("blockno", &number.to_string()),
+                ];
+                self.get::<Amount>(parameters).await
+            }
+            block => {
+                let parameters = &[
+                    (MODULE, ACCOUNT),
+                    (ACTION, "balance"),
+                    (ADDRESS, &TypeExtensions::format(address)),
+                    (TAG, &block.to_string()),
+                ];
+                self.get::<Amount>(parameters).await
+            }
+        };
+        balance.map(|amount| amount.0)
     }
 
     async fn balances(&self, addresses: Vec<&Address>, tag: Option<Tag>) -> Result<Vec<Balance>> {
@@ -192,7 +307,7 @@ impl Accounts for Client {
             (MODULE, ACCOUNT),
             (ACTION, "balancemulti"),
             (ADDRESS, addresses.as_str()),
-            (TAG, tag.or(Some(Tag::Latest)).unwrap().to_string()),
+            (TAG, tag.unwrap_or(Tag::Latest).to_string()),
         ];
 
         self.get::<Vec<Balance>>(parameters).await
@@ -212,6 +327,18 @@ impl Accounts for Client {
             .await
     }
 
+    fn transactions_stream<'a>(&'a self, address: &'a Address, options: TransactionOptions) -> PageStream<'a, Transaction> {
+        let first = options.page().cloned().unwrap_or(Page::new(1, PAGE_SIZE));
+        let block = |record: &Transaction| Some(record.block_number.as_u64());
+        boxed(paginate(first, block, move |page, from_block| {
+            self.get_transactions_with_options::<Transaction>(
+                TRANSACTIONS,
+                address,
+                options.clone().with_page(page).starting_from(from_block),
+            )
+        }))
+    }
+
     async fn internal_transactions(&self, address: &Address) -> Result<Vec<InternalTransaction>> {
         let parameters = &[
             (MODULE, ACCOUNT),
@@ -235,14 +362,35 @@ impl Accounts for Client {
             .await
     }
 
-    async fn erc20_token_balance(&self, address: &Address, contract_address: &Address) -> Result<u128> {
+    fn internal_transactions_stream<'a>(
+        &'a self,
+        address: &'a Address,
+        options: TransactionOptions,
+    ) -> PageStream<'a, InternalTransaction> {
+        let first = options.page().cloned().unwrap_or(Page::new(1, PAGE_SIZE));
+        let block = |record: &InternalTransaction| Some(record.block_number.as_u64());
+        boxed(paginate(first, block, move |page, from_block| {
+            self.get_transactions_with_options::<InternalTransaction>(
+                INTERNAL_TRANSACTIONS,
+                address,
+                options.clone().with_page(page).starting_from(from_block),
+            )
+        }))
+    }
+
+    async fn erc20_token_balance(&self, address: &Address, contract_address: &Address) -> Result<U256> {
         let parameters = &[
             (MODULE, ACCOUNT),
             (ACTION, "tokenbalance"),
             (ADDRESS, &TypeExtensions::format(address)),
             (CONTRACT_ADDRESS, &TypeExtensions::format(contract_address)),
         ];
-        self.get::<String>(parameters).await.map(|v| v.parse::<u128>().unwrap_or(0))
+        self.get::<Amount>(parameters).await.map(|amount| amount.0)
+    }
+
+    async fn erc20_token_amount(&self, address: &Address, contract_address: &Address, decimals: u8, symbol: &str) -> Result<TokenAmount> {
+        let balance = self.erc20_token_balance(address, contract_address).await?;
+        Ok(TokenAmount::new(balance, decimals, symbol))
     }
 
     async fn erc20_token_transfers(&self, address: &Address, contract_address: &Address) -> Result<Vec<ERC20TokenTransfer>> {
@@ -278,6 +426,17 @@ impl Accounts for Client {
             .await
     }
 
+    fn erc20_token_transfers_stream<'a>(&'a self, options: TokenOptions<'a>) -> PageStream<'a, ERC20TokenTransfer> {
+        let first = options.page().cloned().unwrap_or(Page::new(1, PAGE_SIZE));
+        let block = |record: &ERC20TokenTransfer| Some(record.block_number.as_u64());
+        boxed(paginate(first, block, move |page, from_block| {
+            self.get_tokens_with_options::<ERC20TokenTransfer>(
+                ERC20_TOKEN_TRANSFERS,
+                options.clone().with_page(page).starting_from(from_block),
+            )
+        }))
+    }
+
     async fn erc721_token_transfers(&self, address: &Address, contract_address: &Address) -> Result<Vec<ERC721TokenTransfer>> {
         let parameters = &[
             (MODULE, ACCOUNT),
@@ -311,6 +470,17 @@ impl Accounts for Client {
             .await
     }
 
+    fn erc721_token_transfers_stream<'a>(&'a self, options: TokenOptions<'a>) -> PageStream<'a, ERC721TokenTransfer> {
+        let first = options.page().cloned().unwrap_or(Page::new(1, PAGE_SIZE));
+        let block = |record: &ERC721TokenTransfer| Some(record.block_number.as_u64());
+        boxed(paginate(first, block, move |page, from_block| {
+            self.get_tokens_with_options::<ERC721TokenTransfer>(
+                ERC721_TOKEN_TRANSFERS,
+                options.clone().with_page(page).starting_from(from_block),
+            )
+        }))
+    }
+
     async fn blocks_mined(&self, address: &Address, block_type: BlockType, page: Page) -> Result<Vec<Block>> {
         let block_type = block_type.to_string();
         let page = page.to_string();
@@ -318,12 +488,114 @@ impl Accounts for Client {
             (MODULE, ACCOUNT),
             (ACTION, "getminedblocks"),
             (ADDRESS, &TypeExtensions::format(address)),
-            ("blocktype", &block_type),
+            ("blocktype", block_type),
             (PAGE, &page.0),
             (OFFSET, &page.1),
         ];
         self.get::<Vec<Block>>(parameters).await
     }
+
+    fn blocks_mined_stream<'a>(&'a self, address: &'a Address, block_type: BlockType, page: Page) -> PageStream<'a, Block> {
+        boxed(paginate(
+            page,
+            |_| None,
+            move |page, _| self.blocks_mined(address, block_type.clone(), page),
+        ))
+    }
+}
+
+/// Returns a stream of the records of consecutive pages, starting from the given page, which ends after the first short
+/// page or error.
+///
+/// The API returns at most [`RESULT_WINDOW`] records for a query, so once reached the stream continues with a new query
+/// from the block of the last record, skipping the records of that block already returned. Records whose queries
+/// cannot start from a block, for which `block` returns none, end the stream instead, as does a query whose records are
+/// all from the same block.
+///
+/// # Arguments
+///
+/// * 'first' - The first page fetched and its size
+/// * 'block' - Returns the block of a record, which a query can start from
+/// * 'fetch' - Fetches a page of records, starting from a block (if any)
+fn paginate<'a, T: 'a, B, F, Fut>(first: Page, block: B, mut fetch: F) -> impl Stream<Item = Result<T>> + 'a
+where
+    B: Fn(&T) -> Option<u64> + Clone + 'a,
+    F: FnMut(Page, Option<u64>) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>>> + 'a,
+{
+    let offset = first.offset;
+    let start = Cursor {
+        number: first.number,
+        from_block: None,
+        skip: 0,
+        last: None,
+    };
+    stream::unfold(Some(start), move |cursor| {
+        let page = cursor.map(|cursor| (fetch(Page::new(cursor.number, offset), cursor.from_block), cursor));
+        let block = block.clone();
+        async move {
+            let (page, mut cursor) = page?;
+            let records = match page.await {
+                Ok(records) => records,
+                Err(e) => return Some((vec![Err(e)], None)),
+            };
+            let full = offset > 0 && records.len() >= offset as usize;
+
+            // Skip the records of the block the query starts from which were returned by the previous query
+            let skip = cursor.skip.min(records.len());
+            cursor.skip -= skip;
+            let records: Vec<_> = records.into_iter().skip(skip).collect();
+            for record in &records {
+                cursor.last = match (block(record), cursor.last) {
+                    (Some(block), Some((last, count))) if block == last => Some((block, count + 1)),
+                    (Some(block), _) => Some((block, 1)),
+                    (None, _) => None,
+                };
+            }
+
+            let next = match cursor.number.checked_add(1) {
+                _ if !full => None,
+                Some(number) if number as u64 * offset as u64 <= RESULT_WINDOW => Some(Cursor { number, ..cursor }),
+                // The stream ends after the last page number, rather than wrapping around
+                None => None,
+                // Continue from the block of the last record, unless no further records can be reached
+                _ => match cursor.last {
+                    Some((last, count)) if Some(last) != cursor.from_block => Some(Cursor {
+                        number: 1,
+                        from_block: Some(last),
+                        skip: count,
+                        last: cursor.last,
+                    }),
+                    _ => None,
+                },
+            };
+            Some((records.into_iter().map(Ok).collect::<Vec<_>>(), next))
+        }
+    })
+    .flat_map(stream::iter)
+}
+
+/// The position of [`paginate`] within the records of a list endpoint.
+#[derive(Clone, Copy)]
+struct Cursor {
+    /// The number of the page to fetch
+    number: u32,
+    /// The block the query starts from, if not the first query
+    from_block: Option<u64>,
+    /// The number of records still to skip, which were returned by the previous query
+    skip: usize,
+    /// The block of the last record returned and the number of records returned from it
+    last: Option<(u64, usize)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn boxed<'a, T>(stream: impl Stream<Item = Result<T>> + Send + 'a) -> PageStream<'a, T> {
+    stream.boxed()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn boxed<'a, T>(stream: impl Stream<Item = Result<T>> + 'a) -> PageStream<'a, T> {
+    stream.boxed_local()
 }
 
 impl Client {
@@ -362,7 +634,7 @@ impl Client {
         let parameter;
         if let Some(sort) = options.sort() {
             parameter = sort.to_string();
-            parameters.push((SORT, &parameter))
+            parameters.push((SORT, parameter))
         }
 
         self.get::<Vec<T>>(&parameters).await
@@ -378,7 +650,7 @@ impl Client {
 
         // Add contract address if provided
         if let Some(contract_address) = options.contract_address() {
-            parameters.push((ADDRESS, contract_address))
+            parameters.push((CONTRACT_ADDRESS, contract_address))
         }
 
         // Add page if provided
@@ -407,7 +679,7 @@ impl Client {
         let parameter;
         if let Some(sort) = options.sort() {
             parameter = sort.to_string();
-            parameters.push((SORT, &parameter))
+            parameters.push((SORT, parameter))
         }
 
         self.get::<Vec<T>>(&parameters).await
@@ -415,25 +687,29 @@ impl Client {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct Balance {
     pub account: Address,
-    #[serde_as(as = "DisplayFromStr")]
-    pub balance: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub balance: U256,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Block {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde_as(as = "TimestampSecondsWithFrac<String>")]
     pub time_stamp: DateTime<Utc>,
-    #[serde_as(as = "DisplayFromStr")]
-    pub block_reward: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub block_reward: U256,
 }
 
+#[derive(Clone, Debug)]
 pub enum BlockType {
     /// Canonical blocks
     Blocks,
@@ -450,19 +726,27 @@ impl BlockType {
     }
 }
 
+/// A page of records, by its number from one and the number of records per page.
+#[derive(Clone, Debug)]
 pub struct Page {
-    number: u8,
+    number: u32,
     offset: u16,
 }
 
+#[derive(Clone, Debug)]
 pub enum Sort {
     Ascending,
     Descending,
 }
 
 impl Page {
-    #![allow(dead_code)]
-    fn new(number: u8, offset: u16) -> Page {
+    /// Creates a new page.
+    ///
+    /// # Arguments
+    ///
+    /// * 'number' - The page number, starting from one
+    /// * 'offset' - The number of records per page
+    pub fn new(number: u32, offset: u16) -> Page {
         Page { number, offset }
     }
 
@@ -1,10 +1,13 @@
 use super::Client;
+use crate::accounts::tokens::TokenOptions;
 use crate::accounts::Accounts;
 use crate::accounts::{transactions::TransactionOptions, BlockType, Page, Sort};
-use crate::{convert, Address, BlockNumber, TransactionHash};
+use crate::convert::Unit;
+use crate::tests::{rate_limit_pause, round_trip, serve_responses, FakeTransport};
+use crate::{convert, Address, BlockId, BlockNumber, Network, TransactionHash, U256};
+use futures::StreamExt;
 use once_cell::sync::Lazy;
 use std::str::FromStr;
-use tokio::time::{sleep, Duration};
 
 const API_KEY: &str = "";
 const ADDRESS: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
@@ -13,13 +16,14 @@ const CONTRACT_ADDRESS: &str = "0x06012c8cf97bead5deae237070f9587f8e7a266d";
 const MINER_ADDRESS: &str = "0x9dd134d14d1e65f84b706d6f205cd5b1cd03a46b";
 const UNUSED_ADDRESS: &str = "0xCBb08a7EF0A81817dD4D018De00311B3d0cF07c6";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn balance() -> Result<(), crate::APIError> {
     let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
-    let balance = convert::wei_to_eth(CLIENT.balance(&address, None).await?);
-    assert_ne!(0f64, balance);
+    let balance = CLIENT.balance(&address, None).await?;
+    assert!(!balance.is_zero());
+    let balance = convert::format_units(balance, Unit::Ether);
     println!("Balance of {} is {} ETH", address, balance);
     Ok(())
 }
@@ -28,7 +32,7 @@ async fn balance() -> Result<(), crate::APIError> {
 async fn balance_zero() -> Result<(), crate::APIError> {
     let address = Address::from_str(UNUSED_ADDRESS).expect("could not parse {UNUSED_ADDRESS} as address");
     let balance = CLIENT.balance(&address, None).await?;
-    assert_eq!(0, balance);
+    assert!(balance.is_zero());
     Ok(())
 }
 
@@ -52,7 +56,7 @@ async fn balances_no_results() -> Result<(), crate::APIError> {
     let balances = CLIENT.balances(vec![&address], None).await?;
     assert_eq!(1, balances.len());
     assert_eq!(address, balances[0].account);
-    assert_eq!(0, balances[0].balance);
+    assert!(balances[0].balance.is_zero());
     Ok(())
 }
 
@@ -79,7 +83,7 @@ async fn transactions_pages() -> Result<(), crate::APIError> {
     assert_eq!(offset as usize, transactions.len());
     let block_number = &transactions[0].block_number;
 
-    sleep(Duration::from_secs(5)).await; // API rate limiting
+    rate_limit_pause().await;
 
     let transactions = CLIENT
         .transactions_with_options(&address, TransactionOptions::new_page(2, offset))
@@ -100,7 +104,7 @@ async fn transactions_sorts() -> Result<(), crate::APIError> {
     assert_eq!(offset as usize, transactions.len());
     let time_stamp = &transactions[0].time_stamp;
 
-    sleep(Duration::from_secs(5)).await; // API rate limiting
+    rate_limit_pause().await;
 
     let transactions = CLIENT
         .transactions_with_options(&address, TransactionOptions::new_page_with_sort(1, offset, Sort::Descending))
@@ -158,7 +162,7 @@ async fn erc20_token_balance() -> Result<(), crate::APIError> {
     let address = Address::from_str("0xe04f27eb70e025b78871a2ad7eabe85e61212761").expect("could not parse as address");
     let contract_address = Address::from_str("0x57d90b64a1a57749b0f932f1a3395792e12e7055").expect("could not parse as address");
     let balance = CLIENT.erc20_token_balance(&address, &contract_address).await?;
-    assert_eq!(135499, balance);
+    assert_eq!(U256::from(135499), balance);
     Ok(())
 }
 
@@ -226,3 +230,336 @@ async fn blocks_mined_no_results() -> Result<(), crate::APIError> {
     assert_eq!(0, blocks.len());
     Ok(())
 }
+
+#[tokio::test]
+async fn transactions_nothing_found() -> Result<(), crate::APIError> {
+    let address = Address::from_str(UNUSED_ADDRESS).expect("could not parse {UNUSED_ADDRESS} as address");
+    assert!(CLIENT.transactions(&address).await?.is_empty());
+    let options = TransactionOptions::new_page(1, 10);
+    assert!(CLIENT.transactions_with_options(&address, options).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn internal_transactions_nothing_found() -> Result<(), crate::APIError> {
+    let address = Address::from_str(UNUSED_ADDRESS).expect("could not parse {UNUSED_ADDRESS} as address");
+    let hash = TransactionHash::zero();
+    assert!(CLIENT.internal_transactions(&address).await?.is_empty());
+    assert!(CLIENT.internal_transactions_for_transaction(&hash).await?.is_empty());
+    let options = TransactionOptions::new_page(1, 10);
+    assert!(CLIENT.internal_transactions_with_options(&address, options).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn erc20_token_transfers_nothing_found() -> Result<(), crate::APIError> {
+    let address = Address::from_str(UNUSED_ADDRESS).expect("could not parse {UNUSED_ADDRESS} as address");
+    let contract_address = Address::from_str(CONTRACT_ADDRESS).expect("could not parse {CONTRACT_ADDRESS} as address");
+    assert!(CLIENT.erc20_token_transfers(&address, &contract_address).await?.is_empty());
+    assert!(CLIENT.erc20_token_transfers_by_address(&address).await?.is_empty());
+    assert!(CLIENT
+        .erc20_token_transfers_by_contract_address(&contract_address)
+        .await?
+        .is_empty());
+    let options = TokenOptions::new_page(1, 10);
+    assert!(CLIENT.erc20_token_transfers_with_options(options).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn erc721_token_transfers_nothing_found() -> Result<(), crate::APIError> {
+    let address = Address::from_str(UNUSED_ADDRESS).expect("could not parse {UNUSED_ADDRESS} as address");
+    let contract_address = Address::from_str(CONTRACT_ADDRESS).expect("could not parse {CONTRACT_ADDRESS} as address");
+    assert!(CLIENT.erc721_token_transfers(&address, &contract_address).await?.is_empty());
+    assert!(CLIENT.erc721_token_transfers_by_address(&address).await?.is_empty());
+    let options = TokenOptions::new_page(1, 10);
+    assert!(CLIENT.erc721_token_transfers_with_options(options).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn blocks_mined_nothing_found() -> Result<(), crate::APIError> {
+    let address = Address::from_str(UNUSED_ADDRESS).expect("could not parse {UNUSED_ADDRESS} as address");
+    assert!(CLIENT.blocks_mined(&address, BlockType::Blocks, Page::new(1, 10)).await?.is_empty());
+    assert!(CLIENT.blocks_mined(&address, BlockType::Uncles, Page::new(1, 10)).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn erc20_token_transfer_amount() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(
+        r#"{"status":"1","message":"OK","result":[{"blockNumber":"4730207","timeStamp":"1513240363","hash":"0xe8c208398bd5ae8e4c237658580db56a2a94dfa0ca382c99b776fa6e7d31d5b4","nonce":"406","blockHash":"0x022c5e6a3d2487a8ccf8946a2ffb74938bf8e5c8a3f6d91b41c56378a02b5c14","from":"0x642ae78fafbb8032da552d619ad43f1d81e4dd7c","contractAddress":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","to":"0x4e83362442b8d1bec281594cea3050c8eb01311c","value":"1234560000","tokenName":"USD Coin","tokenSymbol":"USDC","tokenDecimal":"6","transactionIndex":"117","gas":"1594500","gasPrice":"1000000000","gasUsed":"1594500","cumulativeGasUsed":"4457265","input":"deprecated","confirmations":"1"}]}"#,
+    );
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let transfers = transport.client().erc20_token_transfers_by_address(&address).await?;
+    assert_eq!("1,234.56 USDC", transfers[0].amount().to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn erc20_token_amount() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"135499"}"#);
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let amount = transport.client().erc20_token_amount(&address, &address, 2, "TKN").await?;
+    assert_eq!("1,354.99 TKN", amount.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn transactions_typed() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(
+        r#"{"status":"1","message":"OK","result":[{"blockNumber":"54092","timeStamp":"1439048640","hash":"0x9c81f44c29ff0226f835cd0a8a2f2a7eca6db52a711f8211b566fd15d3e0e8d4","nonce":"0","blockHash":"0xd3cabad6adab0b52eb632c386ea194036805713682c62cb589b5abcd76de2159","transactionIndex":"0","from":"0x5abfec25f74cd88437631a7731906932776356f9","to":"","value":"11901464239480000000000000","gas":"2000000","gasPrice":"10000000000000","isError":"0","txreceipt_status":"","input":"0x6060","contractAddress":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","cumulativeGasUsed":"1436963","gasUsed":"1436963","confirmations":"14489096"}]}"#,
+    );
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let transactions = transport.client().transactions(&address).await?;
+    let transaction = &transactions[0];
+    assert_eq!(
+        Address::from_str("0x5abfec25f74cd88437631a7731906932776356f9").expect("could not parse address"),
+        transaction.from
+    );
+    assert_eq!(None, transaction.to);
+    assert_eq!(Some(address), transaction.contract_address);
+    assert_eq!(vec![0x60, 0x60], transaction.input);
+    Ok(())
+}
+
+#[tokio::test]
+async fn internal_transactions_typed() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(
+        r#"{"status":"1","message":"OK","result":[{"blockNumber":"2535479","timeStamp":"1477837690","hash":"0x8a1a9989bda84f80143181a68bc137ecefa64d0d4ebde45dd94fc0cf49e70cb6","from":"0x20d42f2e99a421147acf198d775395cac2e8b03d","to":"0x2c1ba59d6f58433fb1eaee7d20b26ed83bda51a3","value":"3000000000000000","contractAddress":"","input":"","type":"call","gas":"0","gasUsed":"0","traceId":"0","isError":"0","errCode":""}]}"#,
+    );
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let transactions = transport.client().internal_transactions(&address).await?;
+    let transaction = &transactions[0];
+    assert_eq!(
+        Some(Address::from_str("0x2c1ba59d6f58433fb1eaee7d20b26ed83bda51a3").expect("could not parse address")),
+        transaction.to
+    );
+    assert_eq!(None, transaction.contract_address);
+    assert!(transaction.input.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn balance_at_block() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"42"}"#);
+    let client = transport.client();
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(
+        U256::from(42),
+        client.balance(&address, Some(BlockNumber::from(8000000).into())).await?
+    );
+    assert_eq!(U256::from(42), client.balance(&address, Some(BlockId::Pending)).await?);
+    let requests = transport.requests.lock().expect("lock poisoned");
+    assert_eq!("balancehistory", requests[0].action);
+    assert!(requests[0].parameters.contains(&("blockno".to_string(), "8000000".to_string())));
+    assert_eq!("balance", requests[1].action);
+    assert!(requests[1].parameters.contains(&("tag".to_string(), "pending".to_string())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn balance_at_finalized_block() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"jsonrpc":"2.0","id":1,"result":"0x2a"}"#);
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let balance = transport.client().balance(&address, Some(BlockId::Finalized)).await?;
+    assert_eq!(U256::from(42), balance);
+    // The proxy accepts the finalized tag directly, without the historical balance endpoint
+    let requests = transport.requests.lock().expect("lock poisoned");
+    assert_eq!(1, requests.len());
+    assert_eq!(
+        ("proxy", "eth_getBalance"),
+        (requests[0].module.as_str(), requests[0].action.as_str())
+    );
+    assert!(requests[0].parameters.contains(&("tag".to_string(), "finalized".to_string())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn balance_at_block_hash() {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"42"}"#);
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let block = BlockId::Hash(crate::BlockHash::repeat_byte(0x11));
+    assert!(matches!(
+        transport.client().balance(&address, Some(block)).await,
+        Err(crate::APIError::UnsupportedBlock { block: b }) if b == block
+    ));
+    assert!(transport.requests.lock().expect("lock poisoned").is_empty());
+}
+
+#[test]
+fn round_trips() {
+    round_trip::<super::Balance>(r#"{"account":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","balance":"40891626854930000000000"}"#);
+    round_trip::<super::Block>(r#"{"blockNumber":"3462296","timeStamp":"1491118514","blockReward":"5194770940000000000"}"#);
+    let transaction = round_trip::<super::transactions::Transaction>(
+        r#"{"blockNumber":"54092","timeStamp":"1439048640","hash":"0x9c81f44c29ff0226f835cd0a8a2f2a7eca6db52a711f8211b566fd15d3e0e8d4","nonce":"0","blockHash":"0xd3cabad6adab0b52eb632c386ea194036805713682c62cb589b5abcd76de2159","transactionIndex":"0","from":"0x5abfec25f74cd88437631a7731906932776356f9","to":"","value":"11901464239480000000000000","gas":"2000000","gasPrice":"10000000000000","isError":"0","txreceipt_status":"","input":"0x6060","contractAddress":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","cumulativeGasUsed":"1436963","gasUsed":"1436963","confirmations":"14489096"}"#,
+    );
+    assert_eq!(2000000, transaction.gas_limit);
+    let transaction = round_trip::<super::transactions::InternalTransaction>(
+        r#"{"blockNumber":"2535479","timeStamp":"1477837690","hash":"0x8a1a9989bda84f80143181a68bc137ecefa64d0d4ebde45dd94fc0cf49e70cb6","from":"0x20d42f2e99a421147acf198d775395cac2e8b03d","to":"0x2c1ba59d6f58433fb1eaee7d20b26ed83bda51a3","value":"3000000000000000","contractAddress":"","input":"","type":"call","gas":"0","gasUsed":"0","traceId":"0","isError":"0","errCode":""}"#,
+    );
+    assert_eq!("call", transaction.transaction_type);
+    round_trip::<super::tokens::ERC20TokenTransfer>(
+        r#"{"blockNumber":"4730207","timeStamp":"1513240363","hash":"0xe8c208398bd5ae8e4c237658580db56a2a94dfa0ca382c99b776fa6e7d31d5b4","nonce":"406","blockHash":"0x022c5e6a3d2487a8ccf8946a2ffb74938bf8e5c8a3f6d91b41c56378a02b5c14","from":"0x642ae78fafbb8032da552d619ad43f1d81e4dd7c","contractAddress":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","to":"0x4e83362442b8d1bec281594cea3050c8eb01311c","value":"1234560000","tokenName":"USD Coin","tokenSymbol":"USDC","tokenDecimal":"6","transactionIndex":"117","gas":"1594500","gasPrice":"1000000000","gasUsed":"1594500","cumulativeGasUsed":"4457265","input":"deprecated","confirmations":"1"}"#,
+    );
+    let transfer = round_trip::<super::tokens::ERC721TokenTransfer>(
+        r#"{"blockNumber":"4708120","timeStamp":"1512907118","hash":"0x031e6968a8de362e4328d60dcc7f72f0d6fc84284c452f63176632177146de66","nonce":"0","blockHash":"0x4be19c278bfaead5cb0bc9476fa632e2447f6e6259e0303af210302d22779a24","from":"0xb1690c08e213a35ed9bab7b318de14420fb57d8c","contractAddress":"0x06012c8cf97bead5deae237070f9587f8e7a266d","to":"0x6975be450864c02b4613023c2152ee0743572325","tokenID":"202106","tokenName":"CryptoKitties","tokenSymbol":"CK","tokenDecimal":"0","transactionIndex":"81","gas":"158820","gasPrice":"40000000000","gasUsed":"60508","cumulativeGasUsed":"4880352","input":"deprecated","confirmations":"7374081"}"#,
+    );
+    assert_eq!("202106", transfer.token_id);
+}
+
+#[tokio::test]
+async fn blocks_mined_stream() -> Result<(), crate::APIError> {
+    let (uri, mut requests) = serve_responses(vec![
+        (200, r#"{"status":"1","message":"OK","result":[{"blockNumber":"1","timeStamp":"1491118514","blockReward":"1"},{"blockNumber":"2","timeStamp":"1491118514","blockReward":"1"}]}"#),
+        (200, r#"{"status":"1","message":"OK","result":[{"blockNumber":"3","timeStamp":"1491118514","blockReward":"1"}]}"#),
+    ])
+    .await;
+    let client = Client::builder("").network(Network::Custom(uri)).build()?;
+    let address = Address::from_str(MINER_ADDRESS).expect("could not parse {MINER_ADDRESS} as address");
+    let blocks = client
+        .blocks_mined_stream(&address, BlockType::Blocks, Page::new(300, 2))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        vec![BlockNumber::from(1), BlockNumber::from(2), BlockNumber::from(3)],
+        blocks.iter().map(|block| block.block_number).collect::<Vec<_>>()
+    );
+    requests.close();
+    let mut pages = Vec::new();
+    while let Some(request) = requests.recv().await {
+        assert!(request.contains("offset=2"));
+        pages.push(["page=300&", "page=301&"].iter().position(|page| request.contains(page)));
+    }
+    // The short page is the last
+    assert_eq!(vec![Some(0), Some(1)], pages);
+    Ok(())
+}
+
+#[tokio::test]
+async fn blocks_mined_stream_window() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(
+        r#"{"status":"1","message":"OK","result":[{"blockNumber":"1","timeStamp":"1491118514","blockReward":"1"},{"blockNumber":"2","timeStamp":"1491118514","blockReward":"1"}]}"#,
+    );
+    let client = transport.client();
+    let address = Address::from_str(MINER_ADDRESS).expect("could not parse {MINER_ADDRESS} as address");
+    let blocks = client
+        .blocks_mined_stream(&address, BlockType::Blocks, Page::new(4999, 2))
+        .collect::<Vec<_>>()
+        .await;
+    // Mined blocks cannot be queried from a block, so the stream ends with the last page of the result window
+    assert_eq!(4, blocks.len());
+    assert_eq!(2, transport.requests.lock().expect("lock poisoned").len());
+    Ok(())
+}
+
+/// Returns a list of transactions as returned by the API, one for each block given.
+fn transactions_response(blocks: impl IntoIterator<Item = u64>) -> String {
+    let transactions = blocks
+        .into_iter()
+        .enumerate()
+        .map(|(index, block)| {
+            format!(
+                r#"{{"blockNumber":"{block}","timeStamp":"1439048640","hash":"{:?}","nonce":"0","blockHash":"{:?}","transactionIndex":"{index}","from":"0x5abfec25f74cd88437631a7731906932776356f9","to":"","value":"0","gas":"21000","gasPrice":"10000000000000","isError":"0","txreceipt_status":"","input":"0x","contractAddress":"","cumulativeGasUsed":"21000","gasUsed":"21000","confirmations":"1"}}"#,
+                TransactionHash::from_low_u64_be(block * 10 + index as u64 % 10),
+                TransactionHash::from_low_u64_be(block)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(r#"{{"status":"1","message":"OK","result":[{}]}}"#, transactions.join(","))
+}
+
+#[tokio::test]
+async fn transactions_stream_window() -> Result<(), crate::APIError> {
+    // The last page of the result window ends with two transactions of block 5000, which the next query starts from
+    let transport = FakeTransport::responses(vec![
+        transactions_response((2..=4999).chain([5000, 5000])),
+        transactions_response([5000, 5000, 5001]),
+    ]);
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let transactions = transport
+        .client()
+        .transactions_stream(&address, TransactionOptions::new_page(2, 5000))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    // The transactions of block 5000 already returned are skipped
+    assert_eq!(5001, transactions.len());
+    assert_eq!(BlockNumber::from(5000), transactions[4999].block_number);
+    assert_eq!(BlockNumber::from(5001), transactions[5000].block_number);
+
+    let requests = transport.requests.lock().expect("lock poisoned");
+    assert_eq!(2, requests.len());
+    let parameter = |key: &str| {
+        requests[1]
+            .parameters
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(Some("5000".to_string()), parameter("startblock"));
+    assert_eq!(Some("1".to_string()), parameter("page"));
+    assert_eq!(Some("5000".to_string()), parameter("offset"));
+    Ok(())
+}
+
+#[test]
+fn options_starting_from() {
+    let ascending = TransactionOptions::new_page(1, 10).starting_from(Some(5));
+    assert_eq!((Some(5), None), (ascending.start_block(), ascending.end_block()));
+    let descending = TransactionOptions::new_page_with_sort(1, 10, Sort::Descending).starting_from(Some(5));
+    assert_eq!((None, Some(5)), (descending.start_block(), descending.end_block()));
+    let unchanged = TransactionOptions::new_block_range(1, 2).starting_from(None);
+    assert_eq!((Some(1), Some(2)), (unchanged.start_block(), unchanged.end_block()));
+}
+
+#[tokio::test]
+async fn token_transfers_stream_parameters() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"0","message":"No transactions found","result":[]}"#);
+    let client = transport.client();
+    let options = || TokenOptions::new(ADDRESS, CONTRACT_ADDRESS, 0, 99999999, Page::new(1, 100), Sort::Ascending);
+    assert!(client.erc20_token_transfers_stream(options()).collect::<Vec<_>>().await.is_empty());
+    assert!(client.erc721_token_transfers_stream(options()).collect::<Vec<_>>().await.is_empty());
+
+    let requests = transport.requests.lock().expect("lock poisoned");
+    assert_eq!(2, requests.len());
+    for request in requests.iter() {
+        let values = |key: &str| {
+            request
+                .parameters
+                .iter()
+                .filter(|(k, _)| k == key)
+                .map(|(_, value)| value.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![ADDRESS], values("address"));
+        assert_eq!(vec![CONTRACT_ADDRESS], values("contractaddress"));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn transactions_stream_error() -> Result<(), crate::APIError> {
+    let (uri, _) = serve_responses(vec![
+        (200, concat!(r#"{"status":"1","message":"OK","result":["#, r#"{"blockNumber":"54092","timeStamp":"1439048640","hash":"0x9c81f44c29ff0226f835cd0a8a2f2a7eca6db52a711f8211b566fd15d3e0e8d4","nonce":"0","blockHash":"0xd3cabad6adab0b52eb632c386ea194036805713682c62cb589b5abcd76de2159","transactionIndex":"0","from":"0x5abfec25f74cd88437631a7731906932776356f9","to":"","value":"0","gas":"2000000","gasPrice":"10000000000000","isError":"0","txreceipt_status":"","input":"0x","contractAddress":"","cumulativeGasUsed":"1436963","gasUsed":"1436963","confirmations":"14489096"}"#, "]}")),
+        (200, r#"{"status":"0","message":"NOTOK","result":"Error! Invalid address format"}"#),
+        (200, r#"{"status":"0","message":"No transactions found","result":[]}"#),
+    ])
+    .await;
+    let client = Client::builder("").network(Network::Custom(uri)).build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let results = client
+        .transactions_stream(&address, TransactionOptions::new_page(1, 1))
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(2, results.len());
+    assert_eq!(
+        BlockNumber::from(54092),
+        results[0].as_ref().expect("expected transaction").block_number
+    );
+    assert!(results[1].is_err());
+    Ok(())
+}
@@ -1,14 +1,18 @@
-use super::{Page, Sort};
-use crate::{Address, BlockHash, BlockNumber, TransactionHash};
+use super::{super::DecimalU256, Page, Sort};
+use crate::convert::TokenAmount;
+use crate::{Address, BlockHash, BlockNumber, TransactionHash, U256};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr, TimestampSecondsWithFrac};
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ERC20TokenTransfer {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde_as(as = "TimestampSecondsWithFrac<String>")]
     pub time_stamp: DateTime<Utc>,
@@ -19,20 +23,20 @@ pub struct ERC20TokenTransfer {
     pub from: Address,
     pub contract_address: Address,
     pub to: Address,
-    /// Value of the token transfer
-    /// NOTE: Can be a very large amount, therefore currently a string
-    pub value: String,
+    /// Value of the token transfer, in the smallest unit of the token
+    #[serde_as(as = "DecimalU256")]
+    pub value: U256,
     pub token_name: String,
     pub token_symbol: String,
     #[serde_as(as = "DisplayFromStr")]
     pub token_decimal: u8,
     #[serde_as(as = "DisplayFromStr")]
     pub transaction_index: u64,
-    #[serde(alias = "gas")]
+    #[serde(rename = "gas", alias = "gasLimit")]
     #[serde_as(as = "DisplayFromStr")]
     pub gas_limit: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    pub gas_price: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub gas_price: U256,
     #[serde_as(as = "DisplayFromStr")]
     pub gas_used: u128,
     #[serde_as(as = "DisplayFromStr")]
@@ -42,11 +46,21 @@ pub struct ERC20TokenTransfer {
     pub confirmations: u128,
 }
 
+impl ERC20TokenTransfer {
+    /// Returns the value of the token transfer, with the decimals and symbol of the token.
+    pub fn amount(&self) -> TokenAmount {
+        TokenAmount::new(self.value, self.token_decimal, self.token_symbol.clone())
+    }
+}
+
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ERC721TokenTransfer {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde_as(as = "TimestampSecondsWithFrac<String>")]
     pub time_stamp: DateTime<Utc>,
@@ -57,7 +71,7 @@ pub struct ERC721TokenTransfer {
     pub from: Address,
     pub contract_address: Address,
     pub to: Address,
-    #[serde(alias = "tokenID")]
+    #[serde(rename = "tokenID", alias = "tokenId")]
     pub token_id: String, // ENS token ids can be very large
     pub token_name: String,
     pub token_symbol: String,
@@ -65,11 +79,11 @@ pub struct ERC721TokenTransfer {
     pub token_decimal: u8,
     #[serde_as(as = "DisplayFromStr")]
     pub transaction_index: u64,
-    #[serde(alias = "gas")]
+    #[serde(rename = "gas", alias = "gasLimit")]
     #[serde_as(as = "DisplayFromStr")]
     pub gas_limit: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    pub gas_price: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub gas_price: U256,
     #[serde_as(as = "DisplayFromStr")]
     pub gas_used: u128,
     #[serde_as(as = "DisplayFromStr")]
@@ -79,7 +93,7 @@ pub struct ERC721TokenTransfer {
     pub confirmations: u128,
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct TokenOptions<'a> {
     address: Option<&'a str>,
     contract_address: Option<&'a str>,
@@ -113,14 +127,14 @@ impl<'a> TokenOptions<'a> {
         }
     }
 
-    pub fn new_page(number: u8, offset: u16) -> TokenOptions<'a> {
+    pub fn new_page(number: u32, offset: u16) -> TokenOptions<'a> {
         TokenOptions {
             page: Some(Page { number, offset }),
             ..Default::default()
         }
     }
 
-    pub fn new_page_with_sort(number: u8, offset: u16, sort: Sort) -> TokenOptions<'a> {
+    pub fn new_page_with_sort(number: u32, offset: u16, sort: Sort) -> TokenOptions<'a> {
         TokenOptions {
             page: Some(Page { number, offset }),
             sort: Some(sort),
@@ -151,4 +165,21 @@ impl<'a> TokenOptions<'a> {
     pub fn sort(&self) -> Option<&Sort> {
         self.sort.as_ref()
     }
+
+    pub(super) fn with_page(mut self, page: Page) -> TokenOptions<'a> {
+        self.page = Some(page);
+        self
+    }
+
+    /// Returns the options starting from a block (if any), which is the start block in ascending order or the end block
+    /// in descending order.
+    pub(super) fn starting_from(mut self, block: Option<u64>) -> TokenOptions<'a> {
+        if let Some(block) = block {
+            match self.sort {
+                Some(Sort::Descending) => self.end_block = Some(block),
+                _ => self.start_block = Some(block),
+            }
+        }
+        self
+    }
 }
@@ -1,14 +1,17 @@
-use super::{super::BoolFromStr, Page, Sort};
-use crate::{Address, BlockHash, BlockNumber, TransactionHash};
+use super::{super::BlankHexBytes, super::BoolFromStr, super::DecimalU256, super::HexBytes, super::OptionalAddress, Page, Sort};
+use crate::{Address, BlockHash, BlockNumber, Bytes, TransactionHash, U256};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr, TimestampSecondsWithFrac};
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde_as(as = "TimestampSecondsWithFrac<String>")]
     pub time_stamp: DateTime<Utc>,
@@ -18,21 +21,26 @@ pub struct Transaction {
     pub block_hash: BlockHash,
     #[serde_as(as = "DisplayFromStr")]
     pub transaction_index: u64,
-    pub from: String,
-    pub to: String,
-    #[serde_as(as = "DisplayFromStr")]
-    pub value: u128,
-    #[serde(alias = "gas")]
+    pub from: Address,
+    /// The recipient, or none for a contract creation
+    #[serde_as(as = "OptionalAddress")]
+    pub to: Option<Address>,
+    #[serde_as(as = "DecimalU256")]
+    pub value: U256,
+    #[serde(rename = "gas", alias = "gasLimit")]
     #[serde_as(as = "DisplayFromStr")]
     pub gas_limit: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    pub gas_price: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub gas_price: U256,
     #[serde_as(as = "BoolFromStr")]
     pub is_error: bool,
-    #[serde(alias = "txreceipt_status")]
+    #[serde(rename = "txreceipt_status", alias = "txReceiptStatus")]
     pub tx_receipt_status: String,
-    pub input: String,
-    pub contract_address: String,
+    #[serde_as(as = "HexBytes")]
+    pub input: Bytes,
+    /// The address of the contract created, or none if not a contract creation
+    #[serde_as(as = "OptionalAddress")]
+    pub contract_address: Option<Address>,
     #[serde_as(as = "DisplayFromStr")]
     pub cumulative_gas_used: u128,
     #[serde_as(as = "DisplayFromStr")]
@@ -42,21 +50,29 @@ pub struct Transaction {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalTransaction {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde_as(as = "TimestampSecondsWithFrac<String>")]
     pub time_stamp: DateTime<Utc>,
     pub hash: Option<TransactionHash>,
     pub from: Address,
-    pub to: Address,
-    #[serde_as(as = "DisplayFromStr")]
-    pub value: u128,
-    pub contract_address: String,
-    pub input: String,
-    #[serde(alias = "type")]
+    /// The recipient, or none for a contract creation
+    #[serde_as(as = "OptionalAddress")]
+    pub to: Option<Address>,
+    #[serde_as(as = "DecimalU256")]
+    pub value: U256,
+    /// The address of the contract created, or none if not a contract creation
+    #[serde_as(as = "OptionalAddress")]
+    pub contract_address: Option<Address>,
+    #[serde_as(as = "BlankHexBytes")]
+    pub input: Bytes,
+    #[serde(rename = "type", alias = "transactionType")]
     pub transaction_type: String,
     #[serde_as(as = "DisplayFromStr")]
     pub gas: u64,
@@ -68,7 +84,7 @@ pub struct InternalTransaction {
     pub err_code: String,
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct TransactionOptions {
     /// * 'start_block' - An optional starting block number.
     start_block: Option<u64>,
@@ -98,14 +114,14 @@ impl TransactionOptions {
         }
     }
 
-    pub fn new_page(number: u8, offset: u16) -> TransactionOptions {
+    pub fn new_page(number: u32, offset: u16) -> TransactionOptions {
         TransactionOptions {
             page: Some(Page { number, offset }),
             ..Default::default()
         }
     }
 
-    pub fn new_page_with_sort(number: u8, offset: u16, sort: Sort) -> TransactionOptions {
+    pub fn new_page_with_sort(number: u32, offset: u16, sort: Sort) -> TransactionOptions {
         TransactionOptions {
             page: Some(Page { number, offset }),
             sort: Some(sort),
@@ -128,4 +144,21 @@ impl TransactionOptions {
     pub fn sort(&self) -> Option<&Sort> {
         self.sort.as_ref()
     }
+
+    pub(super) fn with_page(mut self, page: Page) -> TransactionOptions {
+        self.page = Some(page);
+        self
+    }
+
+    /// Returns the options starting from a block (if any), which is the start block in ascending order or the end block
+    /// in descending order.
+    pub(super) fn starting_from(mut self, block: Option<u64>) -> TransactionOptions {
+        if let Some(block) = block {
+            match self.sort {
+                Some(Sort::Descending) => self.end_block = Some(block),
+                _ => self.start_block = Some(block),
+            }
+        }
+        self
+    }
 }
@@ -1,11 +1,10 @@
 use super::Result;
-use crate::{APIError, BlockNumber, Client, ACTION, MODULE};
+use crate::{BlockNumber, Client, DecimalU256, ACTION, MODULE, U256};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use ethabi::Address;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr, TimestampSecondsWithFrac};
-use std::str::FromStr;
 
 #[cfg(test)]
 mod tests;
@@ -48,12 +47,7 @@ impl Blocks for Client {
             ("timestamp", &time.timestamp().to_string()),
             ("closest", closest.to_string()),
         ];
-        let value = self.get::<String>(parameters).await?;
-        u64::from_str(&value)
-            .map(|v| BlockNumber::from(v))
-            .map_err(|_| APIError::DeserializationError {
-                message: "unable to deserialize result as block number".to_string(),
-            })
+        self.get::<BlockNumberFromStr>(parameters).await.map(|block_number| block_number.0)
     }
 
     async fn estimated_time(&self, block_number: &BlockNumber) -> Result<EstimatedTime> {
@@ -71,46 +65,62 @@ impl Blocks for Client {
     }
 }
 
+/// A block number, returned as a decimal string.
+#[derive(Deserialize)]
+struct BlockNumberFromStr(#[serde(deserialize_with = "crate::de_string_to_block_number")] BlockNumber);
+
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Block {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde_as(as = "TimestampSecondsWithFrac<String>")]
     pub time_stamp: DateTime<Utc>,
     pub block_miner: Address,
-    #[serde_as(as = "DisplayFromStr")]
-    pub block_reward: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub block_reward: U256,
     pub uncles: Vec<Uncle>,
-    #[serde_as(as = "DisplayFromStr")]
-    pub uncle_inclusion_reward: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub uncle_inclusion_reward: U256,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct EstimatedTime {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub current_block: BlockNumber,
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub countdown_block: BlockNumber,
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub remaining_block: BlockNumber,
     #[serde_as(as = "DisplayFromStr")]
     pub estimate_time_in_sec: f32,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Uncle {
     pub miner: Address,
     #[serde_as(as = "DisplayFromStr")]
     pub uncle_position: u16,
     #[serde(rename = "blockreward")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub block_reward: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub block_reward: U256,
 }
 
 pub enum Closest {
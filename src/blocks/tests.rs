@@ -1,25 +1,26 @@
 use super::Client;
 use crate::blocks::Blocks;
 use crate::blocks::Closest;
+use crate::tests::round_trip;
 use crate::BlockNumber;
 use chrono::{TimeZone, Utc};
 use once_cell::sync::Lazy;
 
 const API_KEY: &str = "";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn at_time() -> Result<(), crate::APIError> {
-    let time = Utc.timestamp(1578638524, 0);
+    let time = Utc.timestamp_opt(1578638524, 0).unwrap();
     let block_number = CLIENT.at_time(time, Closest::Before).await?;
-    assert_eq!(BlockNumber::from(9251482 as u64), block_number);
+    assert_eq!(BlockNumber::from(9251482_u64), block_number);
     Ok(())
 }
 
 #[tokio::test]
 async fn estimated_time() -> Result<(), crate::APIError> {
-    let block_number = BlockNumber::from(16701588 as u64);
+    let block_number = BlockNumber::from(16701588_u64);
     let estimated_time = CLIENT.estimated_time(&block_number).await?;
     assert_eq!(block_number, estimated_time.countdown_block);
     println!("Estimated time remaining until block {} is\n{:#?}", block_number, estimated_time);
@@ -28,9 +29,20 @@ async fn estimated_time() -> Result<(), crate::APIError> {
 
 #[tokio::test]
 async fn reward() -> Result<(), crate::APIError> {
-    let block_number = BlockNumber::from(2165403 as u64);
+    let block_number = BlockNumber::from(2165403_u64);
     let block = CLIENT.reward(&block_number).await?;
     assert_eq!(block_number, block.block_number);
     println!("Reward of block {} is\n{:#?}", block_number, block);
     Ok(())
 }
+
+#[test]
+fn round_trips() {
+    let block = round_trip::<super::Block>(
+        r#"{"blockNumber":"2165403","timeStamp":"1472533979","blockMiner":"0x13a06d3dfe21e0db5c016c03ea7d2509f7f8d1e3","blockReward":"5314181600000000000","uncles":[{"miner":"0xbcdfc35b86bedf72f0cda046a3c16829a2ef41d1","unclePosition":"0","blockreward":"3750000000000000000"},{"miner":"0x0d0c9855c722ff0c78f21e43aa275a5b8ea60dce","unclePosition":"1","blockreward":"3750000000000000000"}],"uncleInclusionReward":"312500000000000000"}"#,
+    );
+    assert_eq!(2, block.uncles.len());
+    round_trip::<super::EstimatedTime>(
+        r#"{"CurrentBlock":"12715477","CountdownBlock":"16701588","RemainingBlock":"3986111","EstimateTimeInSec":"1264282.5"}"#,
+    );
+}
@@ -0,0 +1,237 @@
+use crate::cache::{Cache, ResponseCache};
+use crate::keys::KeyPool;
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::transport::{HttpTransport, Transport};
+use crate::{Client, Network, Result, KEY_COOLDOWN};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A builder for a [`Client`], to configure the network and the underlying transport.
+///
+/// # Example
+///
+/// ```no_run
+/// # use etherscan::{Client, Network};
+/// # use reqwest::header::HeaderValue;
+/// # use std::time::Duration;
+/// let client = Client::builder("API_KEY")
+///     .network(Network::Sepolia)
+///     .timeout(Duration::from_secs(10))
+///     .user_agent(HeaderValue::from_static("my-app/1.0"))
+///     .build()
+///     .expect("could not build client");
+/// ```
+pub struct ClientBuilder {
+    api_keys: Vec<String>,
+    key_cooldown: Duration,
+    network: Network,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    headers: HeaderMap,
+    proxies: Vec<reqwest::Proxy>,
+    http_client: Option<reqwest::Client>,
+    transport: Option<Arc<dyn Transport>>,
+    rate_limit: Option<RateLimit>,
+    retry_policy: Option<RetryPolicy>,
+    cache: Option<(Arc<dyn Cache>, Duration)>,
+}
+
+impl ClientBuilder {
+    pub(crate) fn new(api_key: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            api_keys: vec![api_key.into()],
+            key_cooldown: KEY_COOLDOWN,
+            network: Network::default(),
+            timeout: None,
+            connect_timeout: None,
+            headers: HeaderMap::new(),
+            proxies: Vec::new(),
+            http_client: None,
+            transport: None,
+            rate_limit: None,
+            retry_policy: None,
+            cache: None,
+        }
+    }
+
+    /// Sets the network requests are sent to, which defaults to mainnet.
+    ///
+    /// # Arguments
+    ///
+    /// * 'network' - The network requests are sent to
+    pub fn network(mut self, network: Network) -> ClientBuilder {
+        self.network = network;
+        self
+    }
+
+    /// Adds another API key, so requests are spread across a pool of keys in turn.
+    ///
+    /// # Arguments
+    ///
+    /// * 'api_key' - An API key
+    pub fn api_key(mut self, api_key: impl Into<String>) -> ClientBuilder {
+        self.api_keys.push(api_key.into());
+        self
+    }
+
+    /// Sets how long an API key is moved aside for after being rejected or reaching its rate limit, which defaults to a
+    /// minute. A delay suggested by the API for a rate limit is used instead, when given.
+    ///
+    /// # Arguments
+    ///
+    /// * 'cooldown' - The cooldown period
+    pub fn key_cooldown(mut self, cooldown: Duration) -> ClientBuilder {
+        self.key_cooldown = cooldown;
+        self
+    }
+
+    /// Sets a timeout for each request, from connecting until the response body has been read.
+    ///
+    /// # Arguments
+    ///
+    /// * 'timeout' - The request timeout
+    pub fn timeout(mut self, timeout: Duration) -> ClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a timeout for connecting to the API.
+    ///
+    /// # Arguments
+    ///
+    /// * 'timeout' - The connection timeout
+    pub fn connect_timeout(mut self, timeout: Duration) -> ClientBuilder {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the user agent sent with each request.
+    ///
+    /// # Arguments
+    ///
+    /// * 'user_agent' - The user agent
+    pub fn user_agent(self, user_agent: HeaderValue) -> ClientBuilder {
+        self.header(USER_AGENT, user_agent)
+    }
+
+    /// Adds a header sent with each request.
+    ///
+    /// # Arguments
+    ///
+    /// * 'name' - The header name
+    /// * 'value' - The header value
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> ClientBuilder {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Adds a proxy through which requests are sent, such as a corporate proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * 'proxy' - The proxy
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> ClientBuilder {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Sets a caller-built HTTP client, such as one shared with the rest of an application to reuse its connection pool.
+    ///
+    /// **Note:** The timeouts, headers and proxies of the builder are ignored, as the HTTP client is used as provided.
+    ///
+    /// # Arguments
+    ///
+    /// * 'client' - The HTTP client
+    pub fn http_client(mut self, client: reqwest::Client) -> ClientBuilder {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets a custom transport, such as an in-memory fake, a recorder or another HTTP stack.
+    ///
+    /// **Note:** The HTTP options of the builder are ignored, as requests are sent by the transport instead.
+    ///
+    /// # Arguments
+    ///
+    /// * 'transport' - The transport
+    pub fn transport(mut self, transport: impl Transport + 'static) -> ClientBuilder {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sets a client-side rate limit, so requests wait for a slot rather than failing with a rate limit error.
+    ///
+    /// The limit is shared by all clones of the client, including those for other networks.
+    ///
+    /// # Arguments
+    ///
+    /// * 'rate_limit' - The rate limit, such as that of an Etherscan API plan
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> ClientBuilder {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Sets a policy for retrying failed requests, which applies to every request made by the client.
+    ///
+    /// # Arguments
+    ///
+    /// * 'retry_policy' - The retry policy
+    pub fn retry(mut self, retry_policy: RetryPolicy) -> ClientBuilder {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Sets a cache for responses, so repeated requests are answered without being sent.
+    ///
+    /// Data which cannot change, such as the ABIs and source code of verified contracts which are not proxies, block
+    /// rewards, and blocks, transactions and receipts at least [`FINALITY_DEPTH`](crate::cache::FINALITY_DEPTH) blocks
+    /// behind the latest block number observed by the client, is cached forever. Other data, such as gas prices, the
+    /// latest block number and the source code of proxies, expires after the TTL. Transactions sent are never cached.
+    ///
+    /// The latest block number is not looked up to decide whether data is final, but observed from the responses to
+    /// [`block_number`](crate::proxy::Proxy::block_number) and [`block`](crate::proxy::Proxy::block). Until the client
+    /// has observed it, blocks, transactions and receipts also expire after the TTL.
+    ///
+    /// # Arguments
+    ///
+    /// * 'cache' - The cache, such as a [`MemoryCache`](crate::cache::MemoryCache) or [`DiskCache`](crate::cache::DiskCache)
+    /// * 'ttl' - How long data which can change is cached for
+    pub fn cache(mut self, cache: impl Cache + 'static, ttl: Duration) -> ClientBuilder {
+        self.cache = Some((Arc::new(cache), ttl));
+        self
+    }
+
+    /// Builds the client.
+    pub fn build(self) -> Result<Client> {
+        let transport: Arc<dyn Transport> = match (self.transport, self.http_client) {
+            (Some(transport), _) => transport,
+            (None, Some(client)) => Arc::new(HttpTransport::new(client)),
+            (None, None) => {
+                let mut builder = reqwest::Client::builder().default_headers(self.headers);
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if let Some(timeout) = self.timeout {
+                        builder = builder.timeout(timeout);
+                    }
+                    if let Some(timeout) = self.connect_timeout {
+                        builder = builder.connect_timeout(timeout);
+                    }
+                    for proxy in self.proxies {
+                        builder = builder.proxy(proxy);
+                    }
+                }
+                Arc::new(HttpTransport::new(builder.build()?))
+            }
+        };
+        Ok(Client {
+            keys: Arc::new(KeyPool::new(self.api_keys, self.key_cooldown)),
+            network: self.network,
+            transport,
+            rate_limiter: self.rate_limit.map(|limit| Arc::new(RateLimiter::new(&limit))),
+            retry_policy: self.retry_policy,
+            cache: self.cache.map(|(cache, ttl)| Arc::new(ResponseCache::new(cache, ttl))),
+        })
+    }
+}
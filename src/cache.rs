@@ -0,0 +1,408 @@
+use crate::transport::Request;
+use crate::TAG;
+#[cfg(not(target_arch = "wasm32"))]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The number of blocks after which a block is treated as final, and so data from it as immutable.
+pub const FINALITY_DEPTH: u64 = 64;
+
+/// A store of raw API responses, keyed by request.
+///
+/// Implementations must be safe to share across threads, as a store is shared by all clones of a client.
+pub trait Cache: Send + Sync {
+    /// Returns the cached body for a key, unless missing or expired.
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The cache key, derived from the module, action and parameters of a request
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores a body for a key.
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The cache key, derived from the module, action and parameters of a request
+    /// * 'body' - The response body
+    /// * 'ttl' - How long the body may be used for, or none if it never expires
+    fn set(&self, key: &str, body: &str, ttl: Option<Duration>);
+}
+
+/// An in-memory cache, which is lost when the process exits.
+///
+/// **Note:** Entries are only removed once found to have expired, so the cache grows with the number of distinct
+/// requests made.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, (String, Option<Instant>)>>,
+}
+
+impl MemoryCache {
+    /// Creates a new, empty cache.
+    pub fn new() -> MemoryCache {
+        MemoryCache::default()
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.lock().expect("cache lock poisoned");
+        match entries.get(key) {
+            Some((_, Some(expires))) if *expires <= Instant::now() => {
+                entries.remove(key);
+                None
+            }
+            Some((body, _)) => Some(body.clone()),
+            None => None,
+        }
+    }
+
+    fn set(&self, key: &str, body: &str, ttl: Option<Duration>) {
+        let expires = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(key.to_string(), (body.to_string(), expires));
+    }
+}
+
+/// An on-disk cache, which persists across processes, storing each entry as a JSON file within a directory.
+///
+/// Failures to read or write entries are treated as cache misses rather than failing requests.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct DiskCache {
+    directory: std::path::PathBuf,
+}
+
+/// An entry of an on-disk cache.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    key: String,
+    /// When the entry expires, in seconds since the Unix epoch
+    expires: Option<u64>,
+    body: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DiskCache {
+    /// Creates a new cache, which is stored within a directory.
+    ///
+    /// # Arguments
+    ///
+    /// * 'directory' - The directory entries are stored within, which is created if missing
+    pub fn new(directory: impl Into<std::path::PathBuf>) -> DiskCache {
+        DiskCache {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        // FNV-1a, so file names are stable across processes
+        let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        self.directory.join(format!("{:016x}.json", hash))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Cache for DiskCache {
+    fn get(&self, key: &str) -> Option<String> {
+        let path = self.path(key);
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let entry: Entry = serde_json::from_str(&contents).ok()?;
+        if entry.key != key {
+            return None;
+        }
+        if entry.expires.is_some_and(|expires| expires <= now()) {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        Some(entry.body)
+    }
+
+    fn set(&self, key: &str, body: &str, ttl: Option<Duration>) {
+        let entry = Entry {
+            key: key.to_string(),
+            expires: ttl.map(|ttl| now() + ttl.as_secs().max(1)),
+            body: body.to_string(),
+        };
+        if let Ok(contents) = serde_json::to_string(&entry) {
+            let _ = std::fs::create_dir_all(&self.directory);
+            let _ = std::fs::write(self.path(key), contents);
+        }
+    }
+}
+
+/// The cache of a client, which decides how long each response may be cached for.
+pub(crate) struct ResponseCache {
+    store: Arc<dyn Cache>,
+    ttl: Duration,
+    /// The latest block number observed, used to decide whether data from a block is final, or zero if none has been
+    /// observed yet (so no data is treated as final)
+    latest_block: AtomicU64,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(store: Arc<dyn Cache>, ttl: Duration) -> ResponseCache {
+        ResponseCache {
+            store,
+            ttl,
+            latest_block: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the cache key of a request, which is its [key](Request::key).
+    pub(crate) fn key(request: &Request) -> String {
+        request.key()
+    }
+
+    pub(crate) fn get(&self, request: &Request) -> Option<String> {
+        self.store.get(&ResponseCache::key(request))
+    }
+
+    /// Stores the body of a successful response, for as long as the data it holds may be used.
+    pub(crate) fn set(&self, request: &Request, body: &str) {
+        if let Some(ttl) = self.lifetime(request, body) {
+            self.store.set(&ResponseCache::key(request), body, ttl);
+        }
+    }
+
+    /// Returns how long a response may be cached for: none if it should not be cached, some none if it never expires,
+    /// otherwise the TTL.
+    fn lifetime(&self, request: &Request, body: &str) -> Option<Option<Duration>> {
+        match (request.module.as_str(), request.action.as_str()) {
+            ("contract", "getabi" | "getsourcecode") if is_verified_source(body) => Some(None),
+            ("block", "getblockreward") => Some(None),
+            ("proxy", "eth_sendRawTransaction") => None,
+            ("proxy", "eth_blockNumber") => {
+                if let Some(number) = result(body).as_ref().and_then(|r| r.as_str()).and_then(parse_quantity) {
+                    self.latest_block.fetch_max(number, Ordering::Relaxed);
+                }
+                Some(Some(self.ttl))
+            }
+            // Data from a block by tag, such as the latest finalized block, changes as the chain grows
+            ("proxy", _)
+                if request
+                    .parameters
+                    .iter()
+                    .any(|(key, value)| key == TAG && parse_quantity(value).is_none()) =>
+            {
+                Some(Some(self.ttl))
+            }
+            ("proxy", _) => {
+                let result = result(body);
+                // The block of the data, from the result itself or the block requested
+                let block = result
+                    .as_ref()
+                    .and_then(|r| r.get("blockNumber").or_else(|| r.get("number")))
+                    .and_then(|n| n.as_str())
+                    .or_else(|| {
+                        request
+                            .parameters
+                            .iter()
+                            .find(|(key, _)| key == TAG)
+                            .map(|(_, value)| value.as_str())
+                    })
+                    .and_then(parse_quantity);
+                if request.action == "eth_getBlockByNumber" {
+                    if let Some(number) = block {
+                        self.latest_block.fetch_max(number, Ordering::Relaxed);
+                    }
+                }
+                match (result, block) {
+                    (Some(result), Some(block)) if !result.is_null() && self.is_final(block) => Some(None),
+                    _ => Some(Some(self.ttl)),
+                }
+            }
+            _ => Some(Some(self.ttl)),
+        }
+    }
+
+    fn is_final(&self, block: u64) -> bool {
+        block.saturating_add(FINALITY_DEPTH) <= self.latest_block.load(Ordering::Relaxed)
+    }
+}
+
+/// Returns the result of a response body, if any.
+fn result(body: &str) -> Option<serde_json::Value> {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()?
+        .get_mut("result")
+        .map(serde_json::Value::take)
+}
+
+/// Returns whether a response body holds the ABI or source code of a verified contract, which never changes, rather than
+/// that of an unverified contract, which may be verified later, or of a proxy, whose implementation may be upgraded.
+fn is_verified_source(body: &str) -> bool {
+    match result(body) {
+        Some(serde_json::Value::String(abi)) => abi.starts_with('['),
+        Some(serde_json::Value::Array(contracts)) => {
+            !contracts.is_empty()
+                && contracts.iter().all(|contract| {
+                    contract["SourceCode"].as_str().is_some_and(|source| !source.is_empty()) && contract["Proxy"].as_str() != Some("1")
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Parses a hex-encoded quantity, such as a block number.
+fn parse_quantity(value: &str) -> Option<u64> {
+    u64::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cache, MemoryCache, ResponseCache};
+    use crate::transport::Request;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn request(module: &str, action: &str, parameters: &[(&str, &str)]) -> Request {
+        Request {
+            uri: "https://api.etherscan.io/v2/api".to_string(),
+            module: module.to_string(),
+            action: action.to_string(),
+            parameters: parameters.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    fn cache() -> ResponseCache {
+        ResponseCache::new(Arc::new(MemoryCache::new()), Duration::from_secs(10))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn memory_cache_expiry() {
+        let cache = MemoryCache::new();
+        cache.set("forever", "a", None);
+        cache.set("volatile", "b", Some(Duration::from_secs(10)));
+        assert_eq!(Some("b".to_string()), cache.get("volatile"));
+        tokio::time::advance(Duration::from_secs(11)).await;
+        assert_eq!(Some("a".to_string()), cache.get("forever"));
+        assert_eq!(None, cache.get("volatile"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn disk_cache() {
+        let directory = std::env::temp_dir().join(format!("etherscan-cache-{}", std::process::id()));
+        let cache = super::DiskCache::new(&directory);
+        assert_eq!(None, cache.get("key"));
+        cache.set("key", "body", None);
+        assert_eq!(Some("body".to_string()), super::DiskCache::new(&directory).get("key"));
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn key_excludes_api_key() {
+        let first = request("contract", "getabi", &[("apikey", "KEY1"), ("address", "0x1")]);
+        let second = request("contract", "getabi", &[("apikey", "KEY2"), ("address", "0x1")]);
+        let third = request("contract", "getabi", &[("apikey", "KEY1"), ("address", "0x2")]);
+        assert_eq!(ResponseCache::key(&first), ResponseCache::key(&second));
+        assert_ne!(ResponseCache::key(&first), ResponseCache::key(&third));
+    }
+
+    #[test]
+    fn key_parameters() {
+        let first = request("proxy", "eth_call", &[("to", "0x1"), ("data", "0x2")]);
+        let reordered = request("proxy", "eth_call", &[("data", "0x2"), ("to", "0x1")]);
+        let ambiguous = request("proxy", "eth_call", &[("to", "0x1&data=0x2")]);
+        assert_eq!(ResponseCache::key(&first), ResponseCache::key(&reordered));
+        assert_ne!(ResponseCache::key(&first), ResponseCache::key(&ambiguous));
+        assert!(ResponseCache::key(&first).contains(r#"[["data","0x2"],["to","0x1"]]"#));
+    }
+
+    #[test]
+    fn lifetime() {
+        let cache = cache();
+        let ttl = Some(Some(Duration::from_secs(10)));
+        assert_eq!(
+            Some(None),
+            cache.lifetime(&request("contract", "getabi", &[]), r#"{"result":"[]"}"#)
+        );
+        assert_eq!(Some(None), cache.lifetime(&request("block", "getblockreward", &[]), "{}"));
+        assert_eq!(ttl, cache.lifetime(&request("gastracker", "gasoracle", &[]), "{}"));
+        assert_eq!(None, cache.lifetime(&request("proxy", "eth_sendRawTransaction", &[]), "{}"));
+    }
+
+    #[test]
+    fn lifetime_source_code() {
+        let cache = cache();
+        let ttl = Some(Some(Duration::from_secs(10)));
+        let abi = request("contract", "getabi", &[]);
+        let source_code = request("contract", "getsourcecode", &[]);
+        let contract = |source: &str, proxy: &str| {
+            format!(
+                r#"{{"status":"1","message":"OK","result":[{{"SourceCode":"{source}","ABI":"[]","Proxy":"{proxy}","Implementation":""}}]}}"#
+            )
+        };
+
+        // Verified
+        assert_eq!(Some(None), cache.lifetime(&source_code, &contract("contract A {}", "0")));
+
+        // Unverified, which may be verified later
+        assert_eq!(ttl, cache.lifetime(&source_code, &contract("", "0")));
+        assert_eq!(
+            ttl,
+            cache.lifetime(
+                &abi,
+                r#"{"status":"1","message":"OK","result":"Contract source code not verified"}"#
+            )
+        );
+
+        // A proxy, whose implementation may be upgraded
+        assert_eq!(ttl, cache.lifetime(&source_code, &contract("contract A {}", "1")));
+    }
+
+    #[test]
+    fn lifetime_receipt() {
+        let cache = cache();
+        let ttl = Some(Some(Duration::from_secs(10)));
+        let receipt = request("proxy", "eth_getTransactionReceipt", &[]);
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":{"blockNumber":"0x100"}}"#;
+
+        // The latest block is unknown
+        assert_eq!(ttl, cache.lifetime(&receipt, body));
+
+        // The block is recent
+        cache.lifetime(&request("proxy", "eth_blockNumber", &[]), r#"{"result":"0x120"}"#);
+        assert_eq!(ttl, cache.lifetime(&receipt, body));
+
+        // The block is final
+        cache.lifetime(&request("proxy", "eth_blockNumber", &[]), r#"{"result":"0x140"}"#);
+        assert_eq!(Some(None), cache.lifetime(&receipt, body));
+
+        // The receipt is not found
+        assert_eq!(ttl, cache.lifetime(&receipt, r#"{"result":null}"#));
+    }
+
+    #[test]
+    fn lifetime_block_by_tag() {
+        let cache = cache();
+        cache.lifetime(&request("proxy", "eth_blockNumber", &[]), r#"{"result":"0x1000"}"#);
+        let count = request("proxy", "eth_getBlockTransactionCountByNumber", &[("tag", "0x10")]);
+        assert_eq!(Some(None), cache.lifetime(&count, r#"{"result":"0x2"}"#));
+        let latest = request("proxy", "eth_getBlockTransactionCountByNumber", &[("tag", "latest")]);
+        assert_eq!(Some(Some(Duration::from_secs(10))), cache.lifetime(&latest, r#"{"result":"0x2"}"#));
+        let finalized = request("proxy", "eth_getBlockByNumber", &[("tag", "finalized")]);
+        assert_eq!(
+            Some(Some(Duration::from_secs(10))),
+            cache.lifetime(&finalized, r#"{"result":{"number":"0x10"}}"#)
+        );
+    }
+}
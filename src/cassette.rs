@@ -0,0 +1,220 @@
+use crate::transport::{Request, Response, Transport};
+use crate::{APIError, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A transport which records requests and their responses to fixture files, or replays them from those files without
+/// any network access, for offline and deterministic tests.
+///
+/// Each request is stored as a JSON file within the cassette directory, named after its module, action and a hash of its
+/// URI, module, action and sorted parameters. The API key is redacted from stored requests and ignored when matching them.
+///
+/// # Example
+///
+/// ```no_run
+/// # use etherscan::{cassette::Cassette, transport::HttpTransport, Client};
+/// // Record responses from the live API
+/// let client = Client::builder("API_KEY")
+///     .transport(Cassette::record("tests/fixtures", HttpTransport::default()))
+///     .build()
+///     .expect("could not build client");
+///
+/// // Replay recorded responses, without network access
+/// let client = Client::builder("")
+///     .transport(Cassette::replay("tests/fixtures"))
+///     .build()
+///     .expect("could not build client");
+/// ```
+pub struct Cassette {
+    directory: PathBuf,
+    mode: Mode,
+}
+
+enum Mode {
+    Record(Arc<dyn Transport>),
+    Replay,
+}
+
+/// A recorded request and its response.
+#[derive(Debug, Deserialize, Serialize)]
+struct Interaction {
+    request: Request,
+    response: Response,
+}
+
+impl Cassette {
+    /// Creates a cassette which sends requests using another transport, recording each request and its response.
+    ///
+    /// # Arguments
+    ///
+    /// * 'directory' - The directory fixture files are written to
+    /// * 'transport' - The transport requests are sent with
+    pub fn record(directory: impl Into<PathBuf>, transport: impl Transport + 'static) -> Cassette {
+        Cassette {
+            directory: directory.into(),
+            mode: Mode::Record(Arc::new(transport)),
+        }
+    }
+
+    /// Creates a cassette which replays recorded responses, failing any request which has not been recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * 'directory' - The directory fixture files are read from
+    pub fn replay(directory: impl Into<PathBuf>) -> Cassette {
+        Cassette {
+            directory: directory.into(),
+            mode: Mode::Replay,
+        }
+    }
+
+    /// Returns the path of the fixture file for a request.
+    fn path(&self, request: &Request) -> PathBuf {
+        self.directory.join(fixture_name(request))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Transport for Cassette {
+    async fn get(&self, request: &Request) -> Result<Response> {
+        let path = self.path(request);
+        match &self.mode {
+            Mode::Record(transport) => {
+                let response = transport.get(request).await?;
+                let interaction = Interaction {
+                    request: request.redacted(),
+                    response,
+                };
+                write(&path, &interaction)?;
+                Ok(interaction.response)
+            }
+            Mode::Replay => {
+                let contents = fs::read_to_string(&path).map_err(|e| APIError::CassetteError {
+                    message: format!(
+                        "no recording for {}/{} at {}: {}",
+                        request.module,
+                        request.action,
+                        path.display(),
+                        e
+                    ),
+                })?;
+                let interaction: Interaction = serde_json::from_str(&contents).map_err(|e| APIError::CassetteError {
+                    message: format!("invalid recording at {}: {}", path.display(), e),
+                })?;
+                // The file name is only a hash, so the recording may be of another request
+                if interaction.request.key() != request.key() {
+                    return Err(APIError::CassetteError {
+                        message: format!(
+                            "recording at {} is of another request: {}",
+                            path.display(),
+                            interaction.request.key()
+                        ),
+                    });
+                }
+                Ok(interaction.response)
+            }
+        }
+    }
+}
+
+fn write(path: &Path, interaction: &Interaction) -> Result<()> {
+    let error = |e: &dyn std::fmt::Display| APIError::CassetteError {
+        message: format!("could not record to {}: {}", path.display(), e),
+    };
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(|e| error(&e))?;
+    }
+    let contents = serde_json::to_string_pretty(interaction).map_err(|e| error(&e))?;
+    fs::write(path, contents).map_err(|e| error(&e))
+}
+
+/// Returns the name of the fixture file for a request, from its module, action and [fingerprint](Request::fingerprint),
+/// so recordings can be replayed with any key but only against the network they were recorded from.
+fn fixture_name(request: &Request) -> String {
+    format!("{}-{}-{:016x}.json", request.module, request.action, request.fingerprint())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cassette;
+    use crate::accounts::Accounts;
+    use crate::tests::FakeTransport;
+    use crate::{APIError, Address, Client, U256};
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    const ADDRESS: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
+
+    fn directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("etherscan-cassette-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() -> Result<(), APIError> {
+        let directory = directory("record-and-replay");
+        let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+
+        let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"42"}"#);
+        let client = Client::builder("SECRET")
+            .transport(Cassette::record(&directory, transport.clone()))
+            .build()?;
+        assert_eq!(U256::from(42), client.balance(&address, None).await?);
+        assert_eq!(1, transport.requests.lock().expect("lock poisoned").len());
+
+        // The API key is not stored
+        let recordings = std::fs::read_dir(&directory)
+            .expect("could not read recordings")
+            .map(|entry| entry.expect("could not read recording").path())
+            .collect::<Vec<_>>();
+        assert_eq!(1, recordings.len());
+        let recording = std::fs::read_to_string(&recordings[0]).expect("could not read recording");
+        assert!(!recording.contains("SECRET"));
+
+        // Replayed with another key
+        let client = Client::builder("").transport(Cassette::replay(&directory)).build()?;
+        assert_eq!(U256::from(42), client.balance(&address, None).await?);
+
+        let _ = std::fs::remove_dir_all(&directory);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_mismatch() -> Result<(), APIError> {
+        let directory = directory("replay-mismatch");
+        let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+        let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"42"}"#);
+        let client = Client::builder("").transport(Cassette::record(&directory, transport)).build()?;
+        client.balance(&address, None).await?;
+
+        // A recording edited to be of another request is not replayed
+        let recording = std::fs::read_dir(&directory)
+            .expect("could not read recordings")
+            .next()
+            .expect("expected recording")
+            .expect("could not read recording")
+            .path();
+        let contents = std::fs::read_to_string(&recording).expect("could not read recording");
+        std::fs::write(&recording, contents.replace(ADDRESS, "0x0000000000000000000000000000000000000000"))
+            .expect("could not write recording");
+        let client = Client::builder("").transport(Cassette::replay(&directory)).build()?;
+        assert!(matches!(client.balance(&address, None).await, Err(APIError::CassetteError { .. })));
+
+        let _ = std::fs::remove_dir_all(&directory);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_missing() -> Result<(), APIError> {
+        let directory = directory("replay-missing");
+        let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+        let client = Client::builder("").transport(Cassette::replay(&directory)).build()?;
+        assert!(matches!(client.balance(&address, None).await, Err(APIError::CassetteError { .. })));
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use super::{BoolFromStr, Result, ACTION, MODULE};
-use crate::{APIError, Client, TypeExtensions, ADDRESS};
+use crate::{Client, TypeExtensions, ADDRESS};
 use async_trait::async_trait;
 use ethabi::Address;
 use serde::de::Error;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{serde_as, DisplayFromStr};
 use std::str;
 
@@ -39,8 +39,7 @@ pub trait Contracts {
 impl Contracts for Client {
     async fn get_abi(&self, address: &Address) -> Result<ABI> {
         let parameters = &[(MODULE, CONTRACT), (ACTION, "getabi"), (ADDRESS, &TypeExtensions::format(address))];
-        let abi: String = self.get(parameters).await?;
-        ABI::load(abi.as_bytes()).map_err(|e| APIError::DeserializationError { message: e.to_string() })
+        self.get::<AbiFromStr>(parameters).await.map(|abi| abi.0)
     }
 
     async fn get_source_code(&self, address: &Address) -> Result<Vec<Contract>> {
@@ -54,12 +53,12 @@ impl Contracts for Client {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Contract {
     pub source_code: String,
     #[serde(rename = "ABI")]
-    #[serde(deserialize_with = "de_string_to_abi")]
+    #[serde(deserialize_with = "de_string_to_abi", serialize_with = "se_abi_to_string")]
     pub abi: ABI,
     pub contract_name: String,
     pub compiler_version: String,
@@ -78,7 +77,16 @@ pub struct Contract {
     pub swarm_source: String,
 }
 
+/// A contract ABI, returned as a JSON string.
+#[derive(Deserialize)]
+struct AbiFromStr(#[serde(deserialize_with = "de_string_to_abi")] ABI);
+
 fn de_string_to_abi<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<ABI, D::Error> {
     let str_val = String::deserialize(deserializer)?;
     ABI::load(str_val.as_bytes()).map_err(D::Error::custom)
 }
+
+fn se_abi_to_string<S: Serializer>(value: &ABI, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    let abi = serde_json::to_string(value).map_err(serde::ser::Error::custom)?;
+    serializer.serialize_str(&abi)
+}
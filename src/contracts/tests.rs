@@ -1,5 +1,6 @@
 use super::Client;
 use crate::contracts::Contracts;
+use crate::tests::round_trip;
 use crate::Address;
 use once_cell::sync::Lazy;
 use std::str::FromStr;
@@ -9,7 +10,7 @@ const ADDRESS: &str = "0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413";
 const BURN_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
 const ERC721_ADDRESS: &str = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn get_abi() -> Result<(), crate::APIError> {
@@ -31,10 +32,10 @@ async fn get_abi_erc721() -> Result<(), crate::APIError> {
 async fn get_abi_unverified() -> Result<(), crate::APIError> {
     let address = Address::from_str(BURN_ADDRESS).expect("could not parse {BURN_ADDRESS} as address");
     if let Err(e) = CLIENT.get_abi(&address).await {
-        assert!(matches!(e, crate::APIError::ContractNotVerified));
+        assert!(matches!(e, crate::APIError::ContractNotVerified { .. }));
         return Ok(());
     }
-    Ok(assert!(false, "expected failure"))
+    panic!("expected failure")
 }
 
 #[tokio::test]
@@ -71,3 +72,12 @@ async fn get_source_code_erc721() -> Result<(), crate::APIError> {
 
     Ok(())
 }
+
+#[test]
+fn round_trips() {
+    // The ABI is given as ethabi serializes it, as it is serialized as a JSON string again
+    let contract = round_trip::<super::Contract>(
+        r#"{"SourceCode":"pragma solidity ^0.4.0;","ABI":"[{\"type\":\"function\",\"name\":\"owner\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\"}],\"constant\":true,\"stateMutability\":\"view\"}]","ContractName":"Owned","CompilerVersion":"v0.4.24+commit.e67f0147","OptimizationUsed":"1","Runs":"200","ConstructorArguments":"","EVMVersion":"Default","Library":"","LicenseType":"","Proxy":"0","Implementation":"","SwarmSource":""}"#,
+    );
+    assert!(contract.abi.function("owner").is_ok());
+}
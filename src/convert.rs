@@ -1,43 +1,602 @@
+use crate::U256;
+use std::fmt;
+use std::str::FromStr;
+
 const WEI_TO_ETH: f64 = 1_000_000_000_000_000_000f64;
 const GWEI_TO_ETH: f64 = 1_000_000_000f64;
 
+/// The largest number of decimals for which one whole unit fits within 256 bits.
+const MAX_DECIMALS: u8 = 77;
+
+/// A unit of an amount, such as ether or a token with a number of decimals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    /// The smallest unit of ether
+    Wei,
+    /// 10^9 wei
+    Gwei,
+    /// 10^18 wei
+    Ether,
+    /// A unit with a number of decimals, such as those of an ERC-20 token
+    Decimals(u8),
+}
+
+impl Unit {
+    /// Returns the number of decimals of the unit, relative to its smallest unit.
+    pub fn decimals(&self) -> u8 {
+        match self {
+            Unit::Wei => 0,
+            Unit::Gwei => 9,
+            Unit::Ether => 18,
+            Unit::Decimals(decimals) => *decimals,
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wei" => Ok(Unit::Wei),
+            "gwei" => Ok(Unit::Gwei),
+            "eth" | "ether" => Ok(Unit::Ether),
+            _ => Err(ConvertError::UnknownUnit(s.to_string())),
+        }
+    }
+}
+
+/// How an amount is rounded when formatted or parsed with fewer decimal places than it has.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Rounds towards zero, truncating the remaining decimal places
+    #[default]
+    Down,
+    /// Rounds away from zero
+    Up,
+    /// Rounds to the nearest value, with halves rounded away from zero
+    Nearest,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    #[error("invalid amount '{0}'")]
+    InvalidAmount(String),
+    #[error("unknown unit '{0}'")]
+    UnknownUnit(String),
+    #[error("amount '{value}' has more than {decimals} decimal places")]
+    TooPrecise { value: String, decimals: u8 },
+    #[error("amount exceeds 256 bits")]
+    Overflow,
+    #[error("amount cannot be converted exactly")]
+    Inexact,
+}
+
+/// Parses an amount with an optional unit, such as '1.5 ether' or '20 gwei', into its smallest unit (wei). An amount
+/// without a unit is in wei.
+///
+/// # Arguments
+///
+/// * 'value' - The amount and unit, separated by whitespace
+pub fn parse(value: &str) -> Result<U256, ConvertError> {
+    let mut parts = value.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(amount), None, None) => parse_units(amount, Unit::Wei),
+        (Some(amount), Some(unit), None) => parse_units(amount, Unit::from_str(unit)?),
+        _ => Err(ConvertError::InvalidAmount(value.to_string())),
+    }
+}
+
+/// Parses a decimal amount in a unit, such as '1.5' ether, into its smallest unit, exactly.
+///
+/// # Arguments
+///
+/// * 'value' - The decimal amount, such as '1.5'
+/// * 'unit' - The unit of the amount
+pub fn parse_units(value: &str, unit: Unit) -> Result<U256, ConvertError> {
+    parse_decimal(value, unit, None)
+}
+
+/// Parses a decimal amount in a unit, such as '1.5' ether, into its smallest unit, rounding any decimal places beyond
+/// those of the unit rather than failing.
+///
+/// # Arguments
+///
+/// * 'value' - The decimal amount, such as '1.5'
+/// * 'unit' - The unit of the amount
+/// * 'rounding' - How any decimal places beyond those of the unit are rounded
+pub fn parse_units_with(value: &str, unit: Unit, rounding: Rounding) -> Result<U256, ConvertError> {
+    parse_decimal(value, unit, Some(rounding))
+}
+
+fn parse_decimal(value: &str, unit: Unit, rounding: Option<Rounding>) -> Result<U256, ConvertError> {
+    let invalid = || ConvertError::InvalidAmount(value.to_string());
+    let (integer, fraction) = value.trim().split_once('.').unwrap_or((value.trim(), ""));
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    // Trailing zeros beyond the decimals of the unit do not lose precision
+    let decimals = unit.decimals() as usize;
+    let fraction = fraction.trim_end_matches('0');
+    let (fraction, round_up) = match rounding {
+        _ if fraction.len() <= decimals => (fraction, false),
+        None => {
+            return Err(ConvertError::TooPrecise {
+                value: value.to_string(),
+                decimals: unit.decimals(),
+            })
+        }
+        Some(rounding) => {
+            // The remaining digits are not all zero, having been trimmed
+            let (fraction, remainder) = fraction.split_at(decimals);
+            let round_up = match rounding {
+                Rounding::Down => false,
+                Rounding::Up => true,
+                Rounding::Nearest => remainder.as_bytes()[0] >= b'5',
+            };
+            (fraction, round_up)
+        }
+    };
+    let digits = format!("{}{:0<decimals$}", integer, fraction);
+    let amount = U256::from_dec_str(&digits).map_err(|_| ConvertError::Overflow)?;
+    if round_up {
+        amount.checked_add(U256::one()).ok_or(ConvertError::Overflow)
+    } else {
+        Ok(amount)
+    }
+}
+
+/// Converts a whole number of one unit to another exactly, such as 1 ether to 1000000000 gwei, failing if the amount
+/// would be truncated or overflow.
+///
+/// # Arguments
+///
+/// * 'value' - The number of 'from' units
+/// * 'from' - The unit of the amount
+/// * 'to' - The unit to convert to
+pub fn convert(value: U256, from: Unit, to: Unit) -> Result<U256, ConvertError> {
+    let (from, to) = (from.decimals(), to.decimals());
+    if from >= to {
+        value.checked_mul(scale(from - to)?).ok_or(ConvertError::Overflow)
+    } else {
+        let (quotient, remainder) = match scale(to - from) {
+            Ok(divisor) => value.div_mod(divisor),
+            // The amount is smaller than the divisor, which exceeds 256 bits
+            Err(_) => (U256::zero(), value),
+        };
+        if remainder.is_zero() {
+            Ok(quotient)
+        } else {
+            Err(ConvertError::Inexact)
+        }
+    }
+}
+
+/// Formats an amount in a unit exactly, without trailing zeros, such as '1.5' for 1.5 ether.
+///
+/// # Arguments
+///
+/// * 'value' - The amount, in the smallest unit of 'unit'
+/// * 'unit' - The unit to format the amount in
+pub fn format_units(value: U256, unit: Unit) -> String {
+    let formatted = format_decimals(value, unit.decimals());
+    if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        formatted
+    }
+}
+
+/// Formats an amount in a unit with a fixed number of decimal places, such as '1.50' for 1.5 ether with a precision of
+/// two, rounding any remaining decimal places.
+///
+/// # Arguments
+///
+/// * 'value' - The amount, in the smallest unit of 'unit'
+/// * 'unit' - The unit to format the amount in
+/// * 'precision' - The number of decimal places
+/// * 'rounding' - How any remaining decimal places are rounded
+pub fn format_units_with(value: U256, unit: Unit, precision: u8, rounding: Rounding) -> String {
+    let decimals = unit.decimals();
+    if precision >= decimals {
+        let formatted = format_decimals(value, decimals);
+        let padding = (precision - decimals) as usize;
+        return match decimals {
+            0 if precision > 0 => format!("{}.{}", formatted, "0".repeat(padding)),
+            _ => format!("{}{}", formatted, "0".repeat(padding)),
+        };
+    }
+
+    let (quotient, remainder, divisor) = match scale(decimals - precision) {
+        Ok(divisor) => {
+            let (quotient, remainder) = value.div_mod(divisor);
+            (quotient, remainder, Some(divisor))
+        }
+        // The amount is smaller than the divisor, which exceeds 256 bits
+        Err(_) => (U256::zero(), value, None),
+    };
+    let round_up = !remainder.is_zero()
+        && match rounding {
+            Rounding::Down => false,
+            Rounding::Up => true,
+            // Compared as remainder >= divisor - remainder, to avoid overflow
+            Rounding::Nearest => divisor.is_some_and(|divisor| remainder >= divisor - remainder),
+        };
+    let rounded = if round_up { quotient + 1 } else { quotient };
+    format_decimals(rounded, precision)
+}
+
+/// An amount of a token, combining its raw value with the decimals and symbol of the token.
+///
+/// # Example
+///
+/// ```
+/// # use etherscan::{convert::TokenAmount, U256};
+/// let amount = TokenAmount::new(U256::from(1_234_560_000u64), 6, "USDC");
+/// assert_eq!("1,234.56 USDC", amount.to_string());
+/// assert_eq!("1,234.6 USDC", format!("{:.1}", amount));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenAmount {
+    /// The amount in the smallest unit of the token
+    pub value: U256,
+    /// The number of decimals of the token
+    pub decimals: u8,
+    /// The symbol of the token, such as 'USDC'
+    pub symbol: String,
+}
+
+impl TokenAmount {
+    /// Creates a token amount.
+    ///
+    /// # Arguments
+    ///
+    /// * 'value' - The amount in the smallest unit of the token
+    /// * 'decimals' - The number of decimals of the token
+    /// * 'symbol' - The symbol of the token
+    pub fn new(value: U256, decimals: u8, symbol: impl Into<String>) -> TokenAmount {
+        TokenAmount {
+            value,
+            decimals,
+            symbol: symbol.into(),
+        }
+    }
+
+    /// Parses a decimal amount of a token, such as '1234.56', exactly.
+    ///
+    /// # Arguments
+    ///
+    /// * 'value' - The decimal amount
+    /// * 'decimals' - The number of decimals of the token
+    /// * 'symbol' - The symbol of the token
+    pub fn parse(value: &str, decimals: u8, symbol: impl Into<String>) -> Result<TokenAmount, ConvertError> {
+        parse_units(value, Unit::Decimals(decimals)).map(|value| TokenAmount::new(value, decimals, symbol))
+    }
+
+    /// Returns the unit of the token.
+    pub fn unit(&self) -> Unit {
+        Unit::Decimals(self.decimals)
+    }
+
+    /// Adds another amount of the same token, returning none if the tokens differ or the sum overflows.
+    pub fn checked_add(&self, other: &TokenAmount) -> Option<TokenAmount> {
+        self.same_token(other)?;
+        self.value.checked_add(other.value).map(|value| self.with_value(value))
+    }
+
+    /// Subtracts another amount of the same token, returning none if the tokens differ or the difference is negative.
+    pub fn checked_sub(&self, other: &TokenAmount) -> Option<TokenAmount> {
+        self.same_token(other)?;
+        self.value.checked_sub(other.value).map(|value| self.with_value(value))
+    }
+
+    /// Multiplies the amount, returning none if the product overflows.
+    pub fn checked_mul(&self, multiplier: U256) -> Option<TokenAmount> {
+        self.value.checked_mul(multiplier).map(|value| self.with_value(value))
+    }
+
+    /// Divides the amount, rounding down, returning none if the divisor is zero.
+    pub fn checked_div(&self, divisor: U256) -> Option<TokenAmount> {
+        self.value.checked_div(divisor).map(|value| self.with_value(value))
+    }
+
+    fn same_token(&self, other: &TokenAmount) -> Option<()> {
+        (self.decimals == other.decimals && self.symbol == other.symbol).then_some(())
+    }
+
+    fn with_value(&self, value: U256) -> TokenAmount {
+        TokenAmount::new(value, self.decimals, self.symbol.clone())
+    }
+}
+
+/// Formats the amount with its integer digits grouped by thousands and its symbol, such as '1,234.56 USDC'. The
+/// precision, if any, sets the number of decimal places, rounded to the nearest value.
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted = match f.precision() {
+            Some(precision) => {
+                let precision = u8::try_from(precision).unwrap_or(u8::MAX);
+                format_units_with(self.value, self.unit(), precision, Rounding::Nearest)
+            }
+            None => format_units(self.value, self.unit()),
+        };
+        let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        let mut amount = String::new();
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                amount.push(',');
+            }
+            amount.push(digit);
+        }
+        if !fraction.is_empty() {
+            amount.push('.');
+            amount.push_str(fraction);
+        }
+        if !self.symbol.is_empty() {
+            amount.push(' ');
+            amount.push_str(&self.symbol);
+        }
+        pad(f, &amount)
+    }
+}
+
+/// Writes a value padded to the width of the formatter, with its fill and alignment, as [`fmt::Formatter::pad`] does
+/// but without truncating the value to the precision, which is the number of decimal places of amounts.
+fn pad(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(value.chars().count());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Left) | None => (0, padding),
+    };
+    let fill = f.fill().to_string();
+    f.write_str(&fill.repeat(before))?;
+    f.write_str(value)?;
+    f.write_str(&fill.repeat(after))
+}
+
+/// Returns 10 to the power of a number of decimals, failing if it exceeds 256 bits.
+fn scale(decimals: u8) -> Result<U256, ConvertError> {
+    if decimals > MAX_DECIMALS {
+        return Err(ConvertError::Overflow);
+    }
+    Ok(U256::exp10(decimals as usize))
+}
+
+/// Formats an amount in its smallest unit with all of the decimal places of its unit.
+fn format_decimals(value: U256, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}.{}", integer, fraction)
+}
+
+#[deprecated(note = "loses precision through f64, use `parse_units` and `format_units` instead")]
 pub fn eth_to_gwei(value: f64) -> u128 {
     (value * GWEI_TO_ETH) as u128
 }
 
+#[deprecated(note = "loses precision through f64, use `parse_units(value, Unit::Ether)` instead")]
 pub fn eth_to_wei(value: f64) -> u128 {
     (value * WEI_TO_ETH) as u128
 }
 
+#[deprecated(note = "loses precision through f64, use `format_units` with the amount in wei instead")]
 pub fn gwei_to_eth(value: u128) -> f64 {
     value as f64 / GWEI_TO_ETH
 }
 
+#[deprecated(note = "loses precision through f64, use `format_units(value, Unit::Ether)` instead")]
 pub fn wei_to_eth(value: u128) -> f64 {
     value as f64 / WEI_TO_ETH
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::convert;
+    use super::{ConvertError, Rounding, TokenAmount, Unit};
+    use crate::{convert, U256};
+
+    fn wei(value: &str) -> U256 {
+        U256::from_dec_str(value).expect("could not parse amount")
+    }
 
     #[test]
+    #[allow(deprecated)]
     pub fn eth_to_gwei() {
         assert_eq!(1000000000, convert::eth_to_gwei(1f64))
     }
 
     #[test]
+    #[allow(deprecated)]
     pub fn eth_to_wei() {
         assert_eq!(1000000000000000000, convert::eth_to_wei(1f64))
     }
 
     #[test]
+    #[allow(deprecated)]
     pub fn gwei_to_eth() {
         assert_eq!(1f64, convert::gwei_to_eth(1000000000))
     }
 
     #[test]
+    #[allow(deprecated)]
     pub fn wei_to_eth() {
         assert_eq!(1f64, convert::wei_to_eth(1000000000000000000))
     }
+
+    #[test]
+    pub fn parse() {
+        assert_eq!(Ok(wei("1500000000000000000")), convert::parse("1.5 ether"));
+        assert_eq!(Ok(wei("1500000000000000000")), convert::parse("1.5 ETH"));
+        assert_eq!(Ok(wei("20000000000")), convert::parse("20 gwei"));
+        assert_eq!(Ok(wei("42")), convert::parse("42"));
+        assert_eq!(Ok(wei("500000000000000000")), convert::parse(".5 ether"));
+        assert_eq!(Err(ConvertError::UnknownUnit("finney".to_string())), convert::parse("1 finney"));
+        assert_eq!(Err(ConvertError::InvalidAmount("".to_string())), convert::parse(""));
+        assert_eq!(
+            Err(ConvertError::InvalidAmount("1 ether extra".to_string())),
+            convert::parse("1 ether extra")
+        );
+    }
+
+    #[test]
+    pub fn parse_units() {
+        assert_eq!(Ok(wei("1234560")), convert::parse_units("1.23456", Unit::Decimals(6)));
+        assert_eq!(Ok(wei("1230000")), convert::parse_units("1.230000000", Unit::Decimals(6)));
+        assert_eq!(Ok(wei("1")), convert::parse_units("1.", Unit::Wei));
+        assert_eq!(
+            Err(ConvertError::TooPrecise {
+                value: "1.5".to_string(),
+                decimals: 0
+            }),
+            convert::parse_units("1.5", Unit::Wei)
+        );
+        for invalid in [".", "-1", "+1", "1e18", "1.2.3", "0x10", "1 000"] {
+            assert_eq!(
+                Err(ConvertError::InvalidAmount(invalid.to_string())),
+                convert::parse_units(invalid, Unit::Ether)
+            );
+        }
+        assert_eq!(Ok(U256::MAX), convert::parse_units(&U256::MAX.to_string(), Unit::Wei));
+        assert_eq!(
+            Err(ConvertError::Overflow),
+            convert::parse_units(&U256::MAX.to_string(), Unit::Gwei)
+        );
+    }
+
+    #[test]
+    pub fn convert() {
+        assert_eq!(Ok(wei("1000000000")), convert::convert(U256::one(), Unit::Ether, Unit::Gwei));
+        assert_eq!(Ok(wei("2")), convert::convert(wei("2000000000"), Unit::Wei, Unit::Gwei));
+        assert_eq!(
+            Err(ConvertError::Inexact),
+            convert::convert(wei("2000000001"), Unit::Wei, Unit::Gwei)
+        );
+        assert_eq!(Err(ConvertError::Overflow), convert::convert(U256::MAX, Unit::Ether, Unit::Wei));
+        assert_eq!(Ok(U256::zero()), convert::convert(U256::zero(), Unit::Wei, Unit::Decimals(100)));
+        assert_eq!(
+            Err(ConvertError::Inexact),
+            convert::convert(U256::MAX, Unit::Wei, Unit::Decimals(100))
+        );
+        assert_eq!(
+            Err(ConvertError::Overflow),
+            convert::convert(U256::one(), Unit::Decimals(100), Unit::Wei)
+        );
+    }
+
+    #[test]
+    pub fn parse_units_with() {
+        let value = "0.123456789123";
+        assert_eq!(Ok(wei("123456789")), convert::parse_units_with(value, Unit::Gwei, Rounding::Down));
+        assert_eq!(Ok(wei("123456790")), convert::parse_units_with(value, Unit::Gwei, Rounding::Up));
+        assert_eq!(
+            Ok(wei("123456789")),
+            convert::parse_units_with(value, Unit::Gwei, Rounding::Nearest)
+        );
+        assert_eq!(Ok(wei("2")), convert::parse_units_with("1.5", Unit::Wei, Rounding::Nearest));
+        assert_eq!(Ok(wei("1")), convert::parse_units_with("1.000", Unit::Wei, Rounding::Up));
+        assert_eq!(
+            Err(ConvertError::Overflow),
+            convert::parse_units_with(&format!("{}.1", U256::MAX), Unit::Wei, Rounding::Up)
+        );
+        assert_eq!(
+            Err(ConvertError::InvalidAmount("1.2.3".to_string())),
+            convert::parse_units_with("1.2.3", Unit::Gwei, Rounding::Down)
+        );
+    }
+
+    #[test]
+    pub fn format_units() {
+        assert_eq!("1.5", convert::format_units(wei("1500000000000000000"), Unit::Ether));
+        assert_eq!("1", convert::format_units(wei("1000000000000000000"), Unit::Ether));
+        assert_eq!("0.000000000000000001", convert::format_units(U256::one(), Unit::Ether));
+        assert_eq!("0", convert::format_units(U256::zero(), Unit::Ether));
+        assert_eq!("100", convert::format_units(wei("100"), Unit::Wei));
+        assert_eq!("1234.56", convert::format_units(wei("1234560000"), Unit::Decimals(6)));
+        assert_eq!(
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+            convert::format_units(U256::MAX, Unit::Ether)
+        );
+    }
+
+    #[test]
+    pub fn token_amount_display() {
+        let amount = TokenAmount::new(wei("1234560000"), 6, "USDC");
+        assert_eq!("1,234.56 USDC", amount.to_string());
+        assert_eq!("1,234.560 USDC", format!("{:.3}", amount));
+        assert_eq!("1,235 USDC", format!("{:.0}", amount));
+        assert_eq!("0.000001 USDC", TokenAmount::new(U256::one(), 6, "USDC").to_string());
+        assert_eq!("123", TokenAmount::new(wei("123"), 0, "").to_string());
+        assert_eq!("   1,234.56 USDC", format!("{:>16}", amount));
+        assert_eq!("1,234.6 USDC**", format!("{:*<14.1}", amount));
+        assert_eq!("-1,235 USDC-", format!("{:-^12.0}", amount));
+        assert_eq!(
+            "1,000,000 DAI",
+            TokenAmount::new(wei("1000000000000000000000000"), 18, "DAI").to_string()
+        );
+    }
+
+    #[test]
+    pub fn token_amount_arithmetic() {
+        let amount = TokenAmount::parse("1.5", 6, "USDC").expect("could not parse amount");
+        assert_eq!(wei("1500000"), amount.value);
+        let sum = amount.checked_add(&amount).expect("expected sum");
+        assert_eq!("3 USDC", sum.to_string());
+        assert_eq!(Some(amount.clone()), sum.checked_sub(&amount));
+        assert_eq!(None, amount.checked_sub(&sum));
+        assert_eq!(None, amount.checked_add(&TokenAmount::new(U256::one(), 6, "USDT")));
+        assert_eq!(None, amount.checked_add(&TokenAmount::new(U256::one(), 18, "USDC")));
+        assert_eq!(
+            None,
+            TokenAmount::new(U256::MAX, 0, "").checked_add(&TokenAmount::new(U256::one(), 0, ""))
+        );
+        assert_eq!(Some(sum.clone()), amount.checked_mul(U256::from(2)));
+        assert_eq!(Some(amount.clone()), sum.checked_div(U256::from(2)));
+        assert_eq!(None, amount.checked_div(U256::zero()));
+    }
+
+    #[test]
+    pub fn token_amount_parse() {
+        let amount = TokenAmount::parse("2", 6, "USDC");
+        assert_eq!(Ok(TokenAmount::new(wei("2000000"), 6, "USDC")), amount);
+        let weth = TokenAmount::parse("1.5", 18, "WETH").expect("expected amount");
+        assert_eq!(wei("1500000000000000000"), weth.value);
+        assert_eq!(Unit::Decimals(18), weth.unit());
+        assert!(matches!(
+            TokenAmount::parse("0.0000001", 6, "USDC"),
+            Err(ConvertError::TooPrecise { .. })
+        ));
+    }
+
+    #[test]
+    pub fn format_units_with() {
+        let amount = wei("1234567890000000000");
+        assert_eq!("1.23", convert::format_units_with(amount, Unit::Ether, 2, Rounding::Down));
+        assert_eq!("1.24", convert::format_units_with(amount, Unit::Ether, 2, Rounding::Up));
+        assert_eq!("1.23", convert::format_units_with(amount, Unit::Ether, 2, Rounding::Nearest));
+        assert_eq!("1.235", convert::format_units_with(amount, Unit::Ether, 3, Rounding::Nearest));
+        assert_eq!("1", convert::format_units_with(amount, Unit::Ether, 0, Rounding::Down));
+        assert_eq!("2", convert::format_units_with(amount, Unit::Ether, 0, Rounding::Up));
+        assert_eq!("0.50", convert::format_units_with(wei("5"), Unit::Decimals(1), 2, Rounding::Down));
+        assert_eq!("5.00", convert::format_units_with(wei("5"), Unit::Wei, 2, Rounding::Down));
+        assert_eq!("1", convert::format_units_with(wei("5"), Unit::Decimals(1), 0, Rounding::Nearest));
+        assert_eq!("0", convert::format_units_with(wei("4"), Unit::Decimals(1), 0, Rounding::Nearest));
+        assert_eq!(
+            "1.0",
+            convert::format_units_with(wei("95"), Unit::Decimals(2), 1, Rounding::Nearest)
+        );
+        assert_eq!("0.1", convert::format_units_with(U256::one(), Unit::Decimals(100), 1, Rounding::Up));
+        assert_eq!(
+            "0.0",
+            convert::format_units_with(U256::MAX, Unit::Decimals(100), 1, Rounding::Nearest)
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use super::{Result, ACTION, MODULE};
-use crate::Client;
+use crate::{Client, GweiU256, U256};
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
 #[cfg(test)]
@@ -17,9 +17,9 @@ pub trait GasTracker {
     /// # Arguments
     ///
     /// * 'gas_price' - the price paid per unit of gas, in wei
-    async fn estimate_time(&self, gas_price: u64) -> Result<u64>;
+    async fn estimate_time(&self, gas_price: U256) -> Result<u64>;
 
-    /// Returns the current Safe, Proposed and Fast gas prices
+    /// Returns the current Safe, Proposed and Fast gas prices and the suggested base fee, in wei
     async fn oracle(&self) -> Result<Oracle>;
 }
 
@@ -31,13 +31,12 @@ impl GasTracker for Client {
     /// # Arguments
     ///
     /// * 'gas_price' - the price paid per unit of gas, in wei
-    async fn estimate_time(&self, gas_price: u64) -> Result<u64> {
+    async fn estimate_time(&self, gas_price: U256) -> Result<u64> {
         let parameters = &[(MODULE, GAS_TRACKER), (ACTION, "gasestimate"), ("gasprice", &gas_price.to_string())];
-        let seconds = self.get::<String>(parameters).await?;
-        Ok(seconds.parse::<u64>().unwrap_or(0))
+        self.get::<Seconds>(parameters).await.map(|seconds| seconds.0)
     }
 
-    /// Returns the current Safe, Proposed and Fast gas prices
+    /// Returns the current Safe, Proposed and Fast gas prices and the suggested base fee, in wei
     async fn oracle(&self) -> Result<Oracle> {
         let parameters = &[(MODULE, GAS_TRACKER), (ACTION, "gasoracle")];
         self.get(parameters).await
@@ -45,20 +44,25 @@ impl GasTracker for Client {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Deserialize)]
+struct Seconds(#[serde_as(as = "DisplayFromStr")] u64);
+
+/// The current gas prices, in wei.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Oracle {
     #[serde_as(as = "DisplayFromStr")]
     pub last_block: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    pub safe_gas_price: u32,
-    #[serde_as(as = "DisplayFromStr")]
-    pub propose_gas_price: u32,
-    #[serde_as(as = "DisplayFromStr")]
-    pub fast_gas_price: u32,
-    #[serde_as(as = "DisplayFromStr")]
+    #[serde_as(as = "GweiU256")]
+    pub safe_gas_price: U256,
+    #[serde_as(as = "GweiU256")]
+    pub propose_gas_price: U256,
+    #[serde_as(as = "GweiU256")]
+    pub fast_gas_price: U256,
+    #[serde_as(as = "GweiU256")]
     #[serde(rename = "suggestBaseFee")]
-    pub suggest_base_fee: f32,
+    pub suggest_base_fee: U256,
     #[serde(rename = "gasUsedRatio")]
     pub gas_used_ratio: String,
 }
@@ -1,14 +1,16 @@
 use super::Client;
 use crate::gas_tracker::GasTracker;
+use crate::tests::round_trip;
+use crate::U256;
 use once_cell::sync::Lazy;
 
 const API_KEY: &str = "";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn estimate_time() -> Result<(), crate::APIError> {
-    let seconds = CLIENT.estimate_time(2000000000000).await?;
+    let seconds = CLIENT.estimate_time(U256::from(2000000000000u64)).await?;
     assert!(seconds > 0);
     println!("Estimated time is {seconds} seconds");
     Ok(())
@@ -17,6 +19,30 @@ async fn estimate_time() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn oracle() -> Result<(), crate::APIError> {
     let oracle = CLIENT.oracle().await?;
+    assert!(oracle.safe_gas_price <= oracle.propose_gas_price && oracle.propose_gas_price <= oracle.fast_gas_price);
     println!("Current gas prices are {:?}", oracle);
     Ok(())
 }
+
+#[test]
+fn round_trips() {
+    let oracle = round_trip::<super::Oracle>(
+        r#"{"LastBlock":"13053741","SafeGasPrice":"20","ProposeGasPrice":"22","FastGasPrice":"24","suggestBaseFee":"19.25","gasUsedRatio":"0.370119078777807,0.8954731,0.550911766666667"}"#,
+    );
+    assert_eq!(U256::from(20_000_000_000u64), oracle.safe_gas_price);
+    assert_eq!(U256::from(19_250_000_000u64), oracle.suggest_base_fee);
+    // Fractions of a gwei are kept exactly
+    let oracle = round_trip::<super::Oracle>(
+        r#"{"LastBlock":"21000000","SafeGasPrice":"0.579826532","ProposeGasPrice":"0.579826532","FastGasPrice":"0.6","suggestBaseFee":"0.579826531","gasUsedRatio":"0.5"}"#,
+    );
+    assert_eq!(U256::from(579_826_532u64), oracle.safe_gas_price);
+    assert_eq!(U256::from(579_826_531u64), oracle.suggest_base_fee);
+
+    // Fractions of a wei are truncated
+    let oracle: super::Oracle = serde_json::from_str(
+        r#"{"LastBlock":"21000000","SafeGasPrice":"0.123456789123","ProposeGasPrice":"1","FastGasPrice":"2","suggestBaseFee":"0.987654321999","gasUsedRatio":"0.5"}"#,
+    )
+    .expect("could not parse oracle");
+    assert_eq!(U256::from(123_456_789u64), oracle.safe_gas_price);
+    assert_eq!(U256::from(987_654_321u64), oracle.suggest_base_fee);
+}
@@ -0,0 +1,260 @@
+use crate::APIError;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A pool of API keys, which requests are spread across in turn.
+///
+/// A key which is rejected as invalid or reaches its rate limit is moved aside for a cooldown period, during which the
+/// other keys are used. If every key is moved aside, the key whose cooldown ends first is used.
+#[derive(Debug)]
+pub(crate) struct KeyPool {
+    cooldown: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    keys: Vec<Key>,
+    next: usize,
+}
+
+#[derive(Debug)]
+struct Key {
+    value: String,
+    requests: u64,
+    rate_limited: u64,
+    rejected: u64,
+    suspended_until: Option<Instant>,
+}
+
+/// The usage of an API key within a pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyUsage {
+    /// The position of the key within the pool
+    pub index: usize,
+    /// The key, masked apart from its last four characters
+    pub key: String,
+    /// The number of requests made with the key
+    pub requests: u64,
+    /// The number of requests which reached the rate limit of the key
+    pub rate_limited: u64,
+    /// The number of requests for which the key was rejected as invalid
+    pub rejected: u64,
+    /// Whether the key is currently moved aside
+    pub suspended: bool,
+}
+
+impl KeyPool {
+    pub(crate) fn new(keys: Vec<String>, cooldown: Duration) -> KeyPool {
+        // Always hold at least one (possibly empty) key, as requests can be made without one
+        let keys = if keys.is_empty() { vec![String::new()] } else { keys };
+        KeyPool {
+            cooldown,
+            state: Mutex::new(State {
+                keys: keys
+                    .into_iter()
+                    .map(|value| Key {
+                        value,
+                        requests: 0,
+                        rate_limited: 0,
+                        rejected: 0,
+                        suspended_until: None,
+                    })
+                    .collect(),
+                next: 0,
+            }),
+        }
+    }
+
+    /// Returns the index and value of the next available key, counting it as used for a request.
+    pub(crate) fn next(&self) -> (usize, String) {
+        let mut state = self.state.lock().expect("key pool lock poisoned");
+        let index = state.available();
+        let count = state.keys.len();
+        state.next = (index + 1) % count;
+        let key = &mut state.keys[index];
+        key.requests += 1;
+        (index, key.value.clone())
+    }
+
+    /// Returns the value of the key the next request would use, without counting it as used.
+    pub(crate) fn peek(&self) -> String {
+        let state = self.state.lock().expect("key pool lock poisoned");
+        state.keys[state.available()].value.clone()
+    }
+
+    /// Records the outcome of a request made with a key, moving the key aside if it was rejected or rate limited.
+    pub(crate) fn record<T>(&self, index: usize, result: &Result<T, APIError>) {
+        let mut state = self.state.lock().expect("key pool lock poisoned");
+        let key = &mut state.keys[index];
+        let cooldown = match result {
+            Err(e) if e.is_rate_limit() => {
+                key.rate_limited += 1;
+                e.retry_delay().unwrap_or(self.cooldown)
+            }
+            Err(APIError::InvalidAPIKey { .. }) => {
+                key.rejected += 1;
+                self.cooldown
+            }
+            _ => return,
+        };
+        key.suspended_until = Some(Instant::now() + cooldown);
+    }
+
+    /// Returns the values of the keys in the pool, in turn order.
+    pub(crate) fn keys(&self) -> Vec<String> {
+        let state = self.state.lock().expect("key pool lock poisoned");
+        state.keys.iter().map(|key| key.value.clone()).collect()
+    }
+
+    /// Returns the usage of each key in the pool.
+    pub(crate) fn usage(&self) -> Vec<KeyUsage> {
+        let state = self.state.lock().expect("key pool lock poisoned");
+        let now = Instant::now();
+        state
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| KeyUsage {
+                index,
+                key: mask(&key.value),
+                requests: key.requests,
+                rate_limited: key.rate_limited,
+                rejected: key.rejected,
+                suspended: key.suspended_until.is_some_and(|until| until > now),
+            })
+            .collect()
+    }
+}
+
+impl State {
+    /// Returns the index of the next available key, in turn order.
+    fn available(&self) -> usize {
+        let now = Instant::now();
+        let count = self.keys.len();
+        (0..count)
+            .map(|offset| (self.next + offset) % count)
+            .find(|i| self.keys[*i].suspended_until.map_or(true, |until| until <= now))
+            .unwrap_or_else(|| {
+                // All keys are moved aside, so use the one available soonest
+                (0..count)
+                    .min_by_key(|i| self.keys[*i].suspended_until)
+                    .expect("key pool is never empty")
+            })
+    }
+}
+
+fn mask(key: &str) -> String {
+    let visible = key.chars().count().saturating_sub(4);
+    key.chars().enumerate().map(|(i, c)| if i < visible { '*' } else { c }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyPool;
+    use crate::APIError;
+    use std::time::Duration;
+
+    fn rate_limit() -> Result<(), APIError> {
+        Err(APIError::RateLimitReached {
+            message: "Max rate limit reached".to_string(),
+            context: Default::default(),
+        })
+    }
+
+    #[test]
+    fn empty() {
+        let pool = KeyPool::new(vec![], Duration::from_secs(60));
+        assert_eq!((0, String::new()), pool.next());
+    }
+
+    #[test]
+    fn rotation() {
+        let pool = KeyPool::new(vec!["KEY1".to_string(), "KEY2".to_string()], Duration::from_secs(60));
+        assert_eq!((0, "KEY1".to_string()), pool.next());
+        assert_eq!((1, "KEY2".to_string()), pool.next());
+        assert_eq!((0, "KEY1".to_string()), pool.next());
+        let usage = pool.usage();
+        assert_eq!(2, usage[0].requests);
+        assert_eq!(1, usage[1].requests);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limited_key_moved_aside() {
+        let pool = KeyPool::new(vec!["KEY1".to_string(), "KEY2".to_string()], Duration::from_secs(60));
+        let (index, _) = pool.next();
+        pool.record(index, &rate_limit());
+        assert_eq!(1, pool.next().0);
+        assert_eq!(1, pool.next().0);
+        let usage = pool.usage();
+        assert_eq!(1, usage[0].rate_limited);
+        assert!(usage[0].suspended);
+        assert!(!usage[1].suspended);
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        assert_eq!(0, pool.next().0);
+        assert!(!pool.usage()[0].suspended);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn suggested_cooldown() {
+        let pool = KeyPool::new(vec!["KEY1".to_string(), "KEY2".to_string()], Duration::from_secs(60));
+        let (index, _) = pool.next();
+        pool.record(
+            index,
+            &Err::<(), _>(APIError::RateLimitReached {
+                message: "Max calls per sec rate limit reached (5/sec)".to_string(),
+                context: Default::default(),
+            }),
+        );
+        assert!(pool.usage()[0].suspended);
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(!pool.usage()[0].suspended);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn all_keys_moved_aside() {
+        let pool = KeyPool::new(vec!["KEY1".to_string(), "KEY2".to_string()], Duration::from_secs(60));
+        let (first, _) = pool.next();
+        pool.record(first, &rate_limit());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let (second, _) = pool.next();
+        pool.record(
+            second,
+            &Err::<(), _>(APIError::InvalidAPIKey {
+                message: "Invalid API Key".to_string(),
+                context: Default::default(),
+            }),
+        );
+        // The first key is available soonest
+        assert_eq!(0, pool.next().0);
+        assert_eq!(1, pool.usage()[1].rejected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn peek() {
+        let pool = KeyPool::new(vec!["KEY1".to_string(), "KEY2".to_string()], Duration::from_secs(60));
+        assert_eq!("KEY1", pool.peek());
+        let (index, _) = pool.next();
+        assert_eq!("KEY2", pool.peek());
+        pool.record(index, &rate_limit());
+        assert_eq!((1, "KEY2".to_string()), pool.next());
+        // The first key is moved aside, so the second is used again
+        assert_eq!("KEY2", pool.peek());
+        assert_eq!(vec![1, 1], pool.usage().iter().map(|usage| usage.requests).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn keys() {
+        let pool = KeyPool::new(vec!["KEY1".to_string(), "KEY2".to_string()], Duration::from_secs(60));
+        pool.next();
+        assert_eq!(vec!["KEY1".to_string(), "KEY2".to_string()], pool.keys());
+    }
+
+    #[test]
+    fn masked() {
+        let pool = KeyPool::new(vec!["ABCDEFGH".to_string()], Duration::from_secs(60));
+        assert_eq!("****EFGH", pool.usage()[0].key);
+    }
+}
@@ -1,125 +1,493 @@
+use cache::ResponseCache;
+use keys::{KeyPool, KeyUsage};
+use rate_limit::RateLimiter;
+use responses::Failure;
+use retry::RetryPolicy;
 use serde::de::Error as SerdeError;
-use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde::{de, de::DeserializeOwned, de::IntoDeserializer, Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{DeserializeAs, SerializeAs};
-use std::error::Error;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::future::Future;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use transport::{HttpTransport, Request, Transport};
 
 pub mod accounts;
 pub mod blocks;
+pub mod builder;
+pub mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cassette;
 pub mod contracts;
 pub mod convert;
 pub mod gas_tracker;
+pub mod keys;
 pub mod proxy;
+pub mod rate_limit;
 pub mod responses;
+pub mod retry;
+pub mod slots;
 pub mod stats;
 pub mod transactions;
+pub mod transport;
 
-const URI: &str = "https://api.etherscan.io/api";
+#[cfg(test)]
+mod tests;
+
+const KEY_COOLDOWN: Duration = Duration::from_secs(60);
+const NOT_FOUND: u16 = 404;
+const TOO_MANY_REQUESTS: u16 = 429;
+const V2_URI: &str = "https://api.etherscan.io/v2/api";
+const CHAIN_ID: &str = "chainid";
 const MODULE: &str = "module";
 const ACTION: &str = "action";
 const ADDRESS: &str = "address";
 const TAG: &str = "tag";
 
 type Result<T> = std::result::Result<T, crate::APIError>;
+/// Parses the body of a response into its result, or the failure returned in its place.
+type Parse<T> = fn(&str) -> serde_json::Result<std::result::Result<T, Failure>>;
 pub type Address = ethabi::Address;
 pub type BlockHash = ethabi::ethereum_types::H256;
+pub type Bloom = ethabi::ethereum_types::Bloom;
 pub type BlockNumber = ethabi::ethereum_types::U64;
+pub type Bytes = ethabi::Bytes;
 pub type TransactionHash = ethabi::ethereum_types::H256;
+pub type U256 = ethabi::ethereum_types::U256;
 
 #[derive(Clone)]
 pub struct Client {
-    pub api_key: String,
-    client: reqwest::Client,
+    keys: Arc<KeyPool>,
+    network: Network,
+    transport: Arc<dyn Transport>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    retry_policy: Option<RetryPolicy>,
+    cache: Option<Arc<ResponseCache>>,
 }
 
 impl Client {
+    /// Creates a new client for Ethereum mainnet.
+    ///
+    /// # Arguments
+    ///
+    /// * 'api_key' - An Etherscan API key
     pub fn new(api_key: impl Into<String>) -> Client {
+        Client::new_with_network(api_key, Network::Mainnet)
+    }
+
+    /// Creates a new client for the given network.
+    ///
+    /// # Arguments
+    ///
+    /// * 'api_key' - An Etherscan API key, or an API key for the explorer of a custom network
+    /// * 'network' - The network requests are sent to
+    pub fn new_with_network(api_key: impl Into<String>, network: Network) -> Client {
         Client {
-            api_key: api_key.into(),
-            client: reqwest::Client::new(),
+            keys: Arc::new(KeyPool::new(vec![api_key.into()], KEY_COOLDOWN)),
+            network,
+            transport: Arc::new(HttpTransport::default()),
+            rate_limiter: None,
+            retry_policy: None,
+            cache: None,
+        }
+    }
+
+    /// Creates a builder to configure a new client, such as its network, timeouts, headers or HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * 'api_key' - An Etherscan API key, or an API key for the explorer of a custom network
+    pub fn builder(api_key: impl Into<String>) -> builder::ClientBuilder {
+        builder::ClientBuilder::new(api_key)
+    }
+
+    /// Returns the network the client sends requests to.
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    /// Returns the API key the next request will be sent with, which is the next key of its pool that has not been moved
+    /// aside.
+    pub fn api_key(&self) -> String {
+        self.keys.peek()
+    }
+
+    /// Returns the API keys of the client, in the order requests are spread across them.
+    pub fn api_keys(&self) -> Vec<String> {
+        self.keys.keys()
+    }
+
+    /// Returns the usage of each API key of the client, such as to see which key has reached its rate limit.
+    pub fn key_usage(&self) -> Vec<KeyUsage> {
+        self.keys.usage()
+    }
+
+    /// Returns a copy of the client which sends requests to another network, sharing the same API keys and transport.
+    ///
+    /// # Arguments
+    ///
+    /// * 'network' - The network requests are sent to
+    pub fn for_network(&self, network: Network) -> Client {
+        Client { network, ..self.clone() }
+    }
+
+    /// Runs the same call against several networks concurrently, returning each result keyed by network.
+    ///
+    /// # Arguments
+    ///
+    /// * 'networks' - The networks to run the call against
+    /// * 'call' - The call to run, given a client for each network
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use etherscan::{accounts::Accounts, Address, Client, Network};
+    /// # async fn example(address: Address) {
+    /// let client = Client::new("API_KEY");
+    /// let balances = client
+    ///     .across_networks(&[Network::Mainnet, Network::Polygon], |client| async move { client.balance(&address, None).await })
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn across_networks<T, F, Fut>(&self, networks: &[Network], call: F) -> HashMap<Network, Result<T>>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let calls = networks.iter().map(|network| {
+            let call = call(self.for_network(network.clone()));
+            async move { (network.clone(), call.await) }
+        });
+        futures::future::join_all(calls).await.into_iter().collect()
+    }
+
+    /// Builds a request for the network, including the API key and chain id (if any).
+    fn request(&self, api_key: &str, parameters: &[(&str, &str)]) -> Request {
+        let mut request = Request {
+            uri: self.network.uri().to_string(),
+            module: String::new(),
+            action: String::new(),
+            parameters: vec![(transport::API_KEY.to_string(), api_key.to_string())],
+        };
+        if let Some(chain_id) = self.network.chain_id() {
+            request.parameters.push((CHAIN_ID.to_string(), chain_id.to_string()));
+        }
+        for (key, value) in parameters {
+            match *key {
+                MODULE => request.module = value.to_string(),
+                ACTION => request.action = value.to_string(),
+                _ => request.parameters.push((key.to_string(), value.to_string())),
+            }
         }
+        request
     }
 
-    async fn get<'de, T: DeserializeOwned>(&self, parameters: &[(&str, &str)]) -> Result<T> {
-        self.client
-            .get(URI)
-            .query(&[("apikey", &self.api_key)])
-            .query(parameters)
-            .send()
-            .await?
-            .json::<responses::Response<T>>()
-            .await
-            .map(|r| r.result)
-            .map_err(|e| APIError::from(e))
+    /// Sends a request, waiting for a slot first if the client is rate limited.
+    async fn send(&self, request: &Request) -> Result<transport::Response> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        let response = self.transport.get(request).await.map_err(|e| match e {
+            APIError::TransportError { source, .. } => APIError::TransportError {
+                source,
+                context: Box::new(RequestContext::unanswered(request)),
+            },
+            e => e,
+        })?;
+        if response.status == TOO_MANY_REQUESTS {
+            return Err(APIError::RateLimitReached {
+                message: "Too Many Requests".to_string(),
+                context: Box::new(RequestContext::new(request, response)),
+            });
+        }
+        if response.status >= 500 {
+            return Err(APIError::ServerError {
+                status: response.status,
+                context: Box::new(RequestContext::new(request, response)),
+            });
+        }
+        Ok(response)
+    }
+
+    /// Makes an attempt at a request with the next API key of the pool, retrying failed attempts according to the retry
+    /// policy (if any).
+    async fn execute<T, F, Fut>(&self, attempt: F) -> Result<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let (key, api_key) = self.keys.next();
+            let result = attempt(api_key).await;
+            self.keys.record(key, &result);
+            if let (Some(policy), Err(e)) = (&self.retry_policy, &result) {
+                if let Some(backoff) = policy.backoff_for(attempts, e) {
+                    tokio::time::sleep(backoff).await;
+                    continue;
+                }
+            }
+            return result;
+        }
+    }
+
+    /// Fetches a request, parsing the body of its response, from the cache (if any) when possible.
+    ///
+    /// Only responses which parse successfully are cached.
+    async fn fetch<T>(&self, parameters: &[(&str, &str)], parse: Parse<T>) -> Result<T> {
+        let request = self.cache.as_ref().map(|_| self.request("", parameters));
+        if let (Some(cache), Some(request)) = (&self.cache, &request) {
+            if let Some(Ok(Ok(value))) = cache.get(request).map(|body| parse(&body)) {
+                return Ok(value);
+            }
+        }
+        let (value, body) = self
+            .execute(|api_key| async move {
+                let request = self.request(&api_key, parameters);
+                let response = self.send(&request).await?;
+                match parse(&response.body) {
+                    Ok(Ok(value)) => Ok((value, response.body)),
+                    Ok(Err(failure)) => Err(APIError::from_failure(failure, RequestContext::new(&request, response))),
+                    Err(e) => Err(APIError::DeserializationError {
+                        message: e.to_string(),
+                        context: Box::new(RequestContext::new(&request, response)),
+                    }),
+                }
+            })
+            .await?;
+        if let (Some(cache), Some(request)) = (&self.cache, &request) {
+            cache.set(request, &body);
+        }
+        Ok(value)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, parameters: &[(&str, &str)]) -> Result<T> {
+        self.fetch(parameters, |body| {
+            serde_json::from_str::<responses::Response<T>>(body).map(|r| r.result)
+        })
+        .await
     }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum APIError {
+    #[error("API error: {message} ({result})")]
+    ApiError {
+        message: String,
+        result: serde_json::Value,
+        context: Box<RequestContext>,
+    },
+    #[error("Cassette error: {message}")]
+    CassetteError { message: String },
     #[error("Contract not verified")]
-    ContractNotVerified,
-    #[error("Deserialization Error")]
-    DeserializationError { message: String },
+    ContractNotVerified { context: Box<RequestContext> },
+    #[error("Deserialization error: {message}")]
+    DeserializationError { message: String, context: Box<RequestContext> },
     #[error("Invalid address")]
-    InvalidAddress,
+    InvalidAddress { context: Box<RequestContext> },
     #[error("Invalid API Key")]
-    InvalidAPIKey { message: String },
+    InvalidAPIKey { message: String, context: Box<RequestContext> },
     #[error("Rate Limit Reached")]
-    RateLimitReached { message: String },
-    #[error("RPC Error")]
-    RPCError { code: i16, message: String },
+    RateLimitReached { message: String, context: Box<RequestContext> },
+    #[error("Server error ({status})")]
+    ServerError { status: u16, context: Box<RequestContext> },
+    #[error("RPC error ({code}): {message}")]
+    RPCError {
+        code: i64,
+        message: String,
+        data: Option<serde_json::Value>,
+        context: Box<RequestContext>,
+    },
     #[error("Too many addresses provided (max 20)")]
     TooManyAddresses,
+    #[error("Block not supported by this endpoint: {block}")]
+    UnsupportedBlock { block: BlockId },
     #[error("Request error")]
     TransportError {
-        #[from]
         source: reqwest::Error,
+        context: Box<RequestContext>,
     },
 }
 
+impl From<reqwest::Error> for APIError {
+    /// Converts an error from sending a request, whose context is filled in once known.
+    fn from(source: reqwest::Error) -> APIError {
+        APIError::TransportError {
+            source,
+            context: Box::default(),
+        }
+    }
+}
+
 impl APIError {
-    fn from(e: reqwest::Error) -> APIError {
-        if e.is_decode() {
-            if let Some(source) = e.source() {
-                let source = source.downcast_ref::<serde_json::Error>().expect("serde_json error expected.");
-                let source_message = source
-                    .to_string()
-                    .replace(&format!(" at line {} column {}", source.line(), source.column()), "");
-                match source_message.as_str() {
-                    "Max rate limit reached, please use API Key for higher rate limit" => {
-                        return APIError::RateLimitReached { message: source_message }
-                    }
-                    "Max rate limit reached" => return APIError::RateLimitReached { message: source_message },
-                    "Invalid API Key" => return APIError::InvalidAPIKey { message: source_message },
-                    "Too many invalid api key attempts, please try again later" => {
-                        return APIError::InvalidAPIKey { message: source_message }
-                    }
-                    "Contract source code not verified" => return APIError::ContractNotVerified,
-                    "Invalid Address format" => return APIError::InvalidAddress,
-                    _ => {}
-                };
-                if source_message.starts_with("rpc error:") {
-                    if let Ok(error) = serde_json::from_str::<RPCError>(&source_message[10..]) {
-                        return APIError::RPCError {
-                            code: error.code,
-                            message: error.message,
-                        };
-                    }
+    /// Returns the context of the request which failed, if it was sent.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            APIError::ApiError { context, .. }
+            | APIError::ContractNotVerified { context }
+            | APIError::DeserializationError { context, .. }
+            | APIError::InvalidAddress { context }
+            | APIError::InvalidAPIKey { context, .. }
+            | APIError::RateLimitReached { context, .. }
+            | APIError::ServerError { context, .. }
+            | APIError::RPCError { context, .. }
+            | APIError::TransportError { context, .. } => Some(context),
+            APIError::CassetteError { .. } | APIError::TooManyAddresses | APIError::UnsupportedBlock { .. } => None,
+        }
+    }
+
+    /// Returns true for errors which are likely to succeed when retried, such as rate limits, timeouts, dropped
+    /// connections and server errors, rather than permanent ones such as an invalid address.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            APIError::RateLimitReached { .. } | APIError::ServerError { .. } => true,
+            APIError::TransportError { source, .. } => {
+                source.is_timeout() || source.is_connect() || source.is_request() || source.is_body()
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if the rate limit of the API key (or of requests without one) was reached.
+    pub fn is_rate_limit(&self) -> bool {
+        matches!(self, APIError::RateLimitReached { .. })
+    }
+
+    /// Returns true if what was requested does not exist, such as the source code of an unverified contract.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            APIError::ContractNotVerified { .. } => true,
+            APIError::ApiError { message, result, .. } => {
+                responses::is_nothing_found(result.as_str().unwrap_or(message)) || responses::is_nothing_found(message)
+            }
+            _ => self.context().is_some_and(|context| context.status == NOT_FOUND),
+        }
+    }
+
+    /// Returns how long to wait before retrying, when suggested by the API.
+    ///
+    /// The delay is taken from the 'Retry-After' header of the response, or from the rate limit given within a rate
+    /// limit error (such as 'Max calls per sec rate limit reached (5/sec)').
+    pub fn retry_delay(&self) -> Option<Duration> {
+        if let Some(retry_after) = self.context().and_then(|context| context.retry_after) {
+            return Some(retry_after);
+        }
+        match self {
+            // The limit resets after the period it is given per
+            APIError::RateLimitReached { message, .. } => {
+                let message = message.to_lowercase();
+                if message.contains("/sec)") {
+                    Some(Duration::from_secs(1))
+                } else if message.contains("/min)") {
+                    Some(Duration::from_secs(60))
+                } else {
+                    None
                 }
-                return APIError::DeserializationError {
-                    message: source.to_string(),
-                };
             }
+            _ => None,
+        }
+    }
+
+    /// Returns the raw revert data of a failed call or gas estimate, if any.
+    pub fn revert_data(&self) -> Option<Vec<u8>> {
+        match self {
+            APIError::RPCError { data: Some(data), .. } => proxy::revert_data(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason a call or gas estimate reverted, decoding `Error(string)` and `Panic(uint256)` revert data.
+    pub fn revert(&self) -> Option<proxy::Revert> {
+        self.revert_data().map(|data| proxy::Revert::decode(&data))
+    }
+
+    /// Returns the reason a call or gas estimate reverted, decoding the custom errors of the contract called as well as
+    /// `Error(string)` and `Panic(uint256)` revert data.
+    ///
+    /// # Arguments
+    ///
+    /// * 'abi' - The ABI of the contract called, such as from [`Contracts::get_abi`](contracts::Contracts::get_abi)
+    pub fn revert_with(&self, abi: &contracts::ABI) -> Option<proxy::Revert> {
+        self.revert_data().map(|data| proxy::Revert::decode_with(&data, abi))
+    }
+
+    /// Creates an error from a failure returned by the API, recognising known failures by their description.
+    fn from_failure(failure: Failure, context: RequestContext) -> APIError {
+        let context = Box::new(context);
+        let (message, result) = match failure {
+            Failure::Rpc { code, message, data } => {
+                return APIError::RPCError {
+                    code,
+                    message,
+                    data,
+                    context,
+                }
+            }
+            Failure::Api { message, result } => (message, result),
+        };
+        // The failure is described by the result when a string, otherwise by the message
+        let description = result.as_str().unwrap_or(&message).to_string();
+        let lowercase = description.to_lowercase();
+        if lowercase.contains("rate limit reached") {
+            APIError::RateLimitReached {
+                message: description,
+                context,
+            }
+        } else if lowercase.contains("invalid api key") || lowercase.starts_with("too many invalid api key attempts") {
+            APIError::InvalidAPIKey {
+                message: description,
+                context,
+            }
+        } else if lowercase == "contract source code not verified" {
+            APIError::ContractNotVerified { context }
+        } else if lowercase.contains("invalid address format") {
+            APIError::InvalidAddress { context }
+        } else {
+            APIError::ApiError { message, result, context }
         }
-        APIError::TransportError { source: e }
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// The context of a request which failed, for diagnosing the failure.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    /// The request, with its API key redacted
+    pub request: Request,
+    /// The HTTP status code of the response, or zero if there was no response
+    pub status: u16,
+    /// The raw body of the response
+    pub body: String,
+    /// How long to wait before retrying, from the 'Retry-After' header of the response (if any)
+    pub retry_after: Option<Duration>,
+}
+
+impl RequestContext {
+    fn new(request: &Request, response: transport::Response) -> RequestContext {
+        RequestContext {
+            request: request.redacted(),
+            status: response.status,
+            body: response.body,
+            retry_after: response.retry_after,
+        }
+    }
+
+    /// Returns the context of a request which was not answered, such as because it timed out.
+    fn unanswered(request: &Request) -> RequestContext {
+        RequestContext {
+            request: request.redacted(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize)]
 struct RPCError {
-    code: i16,
+    code: i64,
     message: String,
+    data: Option<serde_json::Value>,
 }
 
 struct BoolFromStr;
@@ -137,10 +505,144 @@ impl SerializeAs<bool> for BoolFromStr {
     }
 }
 
+/// A 256-bit amount, such as in wei or token units, as a decimal string.
+///
+/// Parsing is strict, failing for empty strings, signs, decimals or amounts which overflow, rather than defaulting to
+/// zero.
+struct DecimalU256;
+
+impl<'de> DeserializeAs<'de, U256> for DecimalU256 {
+    fn deserialize_as<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_decimal_u256(&value).map_err(D::Error::custom)
+    }
+}
+
+impl SerializeAs<U256> for DecimalU256 {
+    fn serialize_as<S: Serializer>(source: &U256, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(source)
+    }
+}
+
+fn parse_decimal_u256(value: &str) -> std::result::Result<U256, String> {
+    if value.is_empty() {
+        return Err("invalid amount: empty string".to_string());
+    }
+    U256::from_dec_str(value).map_err(|e| format!("invalid amount '{}': {}", value, e))
+}
+
+/// A 256-bit amount in wei, as a decimal string of gwei such as '19.25', truncating any fractions of a wei.
+struct GweiU256;
+
+impl<'de> DeserializeAs<'de, U256> for GweiU256 {
+    fn deserialize_as<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        convert::parse_units_with(&value, convert::Unit::Gwei, convert::Rounding::Down).map_err(D::Error::custom)
+    }
+}
+
+impl SerializeAs<U256> for GweiU256 {
+    fn serialize_as<S: Serializer>(source: &U256, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&convert::format_units(*source, convert::Unit::Gwei))
+    }
+}
+
+/// An address which is empty when absent, such as the recipient of a contract creation.
+struct OptionalAddress;
+
+impl<'de> DeserializeAs<'de, Option<Address>> for OptionalAddress {
+    fn deserialize_as<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<Address>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        if value.is_empty() {
+            return Ok(None);
+        }
+        Address::deserialize(value.into_deserializer()).map(Some)
+    }
+}
+
+impl SerializeAs<Option<Address>> for OptionalAddress {
+    fn serialize_as<S: Serializer>(source: &Option<Address>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match source {
+            Some(address) => address.serialize(serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+}
+
+/// Bytes as a hex string, which may be empty or prefixed with '0x'.
+struct HexBytes;
+
+impl<'de> DeserializeAs<'de, Bytes> for HexBytes {
+    fn deserialize_as<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Bytes, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        hex::decode(value.strip_prefix("0x").unwrap_or(&value)).map_err(D::Error::custom)
+    }
+}
+
+impl SerializeAs<Bytes> for HexBytes {
+    fn serialize_as<S: Serializer>(source: &Bytes, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(source)))
+    }
+}
+
+/// Bytes as a hex string, which is empty rather than '0x' when there are no bytes, as sent for the input of internal
+/// transactions.
+struct BlankHexBytes;
+
+impl<'de> DeserializeAs<'de, Bytes> for BlankHexBytes {
+    fn deserialize_as<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Bytes, D::Error> {
+        HexBytes::deserialize_as(deserializer)
+    }
+}
+
+impl SerializeAs<Bytes> for BlankHexBytes {
+    fn serialize_as<S: Serializer>(source: &Bytes, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if source.is_empty() {
+            return serializer.serialize_str("");
+        }
+        HexBytes::serialize_as(source, serializer)
+    }
+}
+
+/// A 256-bit amount returned as the result of a request, such as a balance in wei.
+#[serde_with::serde_as]
+#[derive(Deserialize)]
+struct Amount(#[serde_as(as = "DecimalU256")] U256);
+
 pub trait TypeExtensions {
     fn format(&self) -> String;
 }
 
+/// Helpers for checking whether a log bloom filter may contain an address or topic.
+///
+/// A bloom filter can return false positives but never false negatives, so a match only means the logs may contain the
+/// address or topic.
+pub trait BloomExtensions {
+    /// Returns whether the logs may contain a log emitted by an address.
+    ///
+    /// # Arguments
+    ///
+    /// * 'address' - The address which emitted the log
+    fn contains_address(&self, address: &Address) -> bool;
+
+    /// Returns whether the logs may contain a log with a topic, such as an event signature.
+    ///
+    /// # Arguments
+    ///
+    /// * 'topic' - The topic
+    fn contains_topic(&self, topic: &ethabi::ethereum_types::H256) -> bool;
+}
+
+impl BloomExtensions for Bloom {
+    fn contains_address(&self, address: &Address) -> bool {
+        self.contains_input(ethabi::ethereum_types::BloomInput::Raw(address.as_bytes()))
+    }
+
+    fn contains_topic(&self, topic: &ethabi::ethereum_types::H256) -> bool {
+        self.contains_input(ethabi::ethereum_types::BloomInput::Raw(topic.as_bytes()))
+    }
+}
+
 impl TypeExtensions for Address {
     fn format(&self) -> String {
         let address: ethabi::Address = self.0.into();
@@ -162,6 +664,12 @@ impl TypeExtensions for BlockNumber {
     }
 }
 
+impl TypeExtensions for U256 {
+    fn format(&self) -> String {
+        format!("{:#x}", self)
+    }
+}
+
 impl TypeExtensions for u64 {
     fn format(&self) -> String {
         format!("{:#x}", self)
@@ -180,6 +688,57 @@ impl TypeExtensions for u16 {
     }
 }
 
+/// A network with an Etherscan (or Etherscan-compatible) explorer API.
+///
+/// All networks other than custom ones are served by the multichain Etherscan V2 API, which selects the network by chain id.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Network {
+    /// Ethereum mainnet
+    #[default]
+    Mainnet,
+    /// Ethereum Sepolia testnet
+    Sepolia,
+    /// Ethereum Holesky testnet
+    Holesky,
+    /// Polygon PoS
+    Polygon,
+    /// Arbitrum One
+    Arbitrum,
+    /// BNB Smart Chain
+    BinanceSmartChain,
+    /// Base
+    Base,
+    /// Any other chain supported by the Etherscan V2 API, by chain id
+    Chain(u64),
+    /// Any other Etherscan-compatible API, by its full URI (e.g. 'http://localhost:8080/api')
+    Custom(String),
+}
+
+impl Network {
+    /// Returns the URI of the explorer API for the network.
+    pub fn uri(&self) -> &str {
+        match self {
+            Network::Custom(uri) => uri,
+            _ => V2_URI,
+        }
+    }
+
+    /// Returns the chain id of the network, or none for a custom network.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            Network::Mainnet => Some(1),
+            Network::Sepolia => Some(11155111),
+            Network::Holesky => Some(17000),
+            Network::Polygon => Some(137),
+            Network::Arbitrum => Some(42161),
+            Network::BinanceSmartChain => Some(56),
+            Network::Base => Some(8453),
+            Network::Chain(chain_id) => Some(*chain_id),
+            Network::Custom(_) => None,
+        }
+    }
+}
+
 pub enum Tag {
     Earliest,
     Pending,
@@ -196,7 +755,67 @@ impl Tag {
     }
 }
 
+/// A block to query state at, by number, hash or tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockId {
+    /// The block with a number, such as for archive reads at a height
+    Number(BlockNumber),
+    /// The block with a hash
+    Hash(BlockHash),
+    /// The genesis block
+    Earliest,
+    /// The latest block
+    Latest,
+    /// The latest block which is unlikely to be reorganised
+    Safe,
+    /// The latest block which has been finalized, and so cannot be reorganised
+    Finalized,
+    /// The pending block
+    Pending,
+}
+
+impl From<BlockNumber> for BlockId {
+    fn from(number: BlockNumber) -> Self {
+        BlockId::Number(number)
+    }
+}
+
+impl From<BlockHash> for BlockId {
+    fn from(hash: BlockHash) -> Self {
+        BlockId::Hash(hash)
+    }
+}
+
+impl From<Tag> for BlockId {
+    fn from(tag: Tag) -> Self {
+        match tag {
+            Tag::Earliest => BlockId::Earliest,
+            Tag::Pending => BlockId::Pending,
+            Tag::Latest => BlockId::Latest,
+        }
+    }
+}
+
+/// Formats the block as a parameter, with numbers and hashes in hex.
+impl std::fmt::Display for BlockId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockId::Number(number) => f.write_str(&number.format()),
+            BlockId::Hash(hash) => f.write_str(&hash.format()),
+            BlockId::Earliest => f.write_str("earliest"),
+            BlockId::Latest => f.write_str("latest"),
+            BlockId::Safe => f.write_str("safe"),
+            BlockId::Finalized => f.write_str("finalized"),
+            BlockId::Pending => f.write_str("pending"),
+        }
+    }
+}
+
 fn de_string_to_block_number<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<BlockNumber, D::Error> {
     let value = String::deserialize(deserializer)?;
-    u64::from_str(&value).map(|v| BlockNumber::from(v)).map_err(D::Error::custom)
+    u64::from_str(&value).map(BlockNumber::from).map_err(D::Error::custom)
+}
+
+fn se_block_number_to_string<S: Serializer>(value: &BlockNumber, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
 }
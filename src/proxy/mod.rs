@@ -1,20 +1,26 @@
-use crate::responses::ResponseStatus;
+use crate::responses::{empty_result, Failure, ResponseStatus};
 use crate::{
-    APIError, Address, BlockHash, BlockNumber, Client, RPCError, Result, TransactionHash, TypeExtensions, ACTION, ADDRESS, MODULE, URI,
+    APIError, Address, BlockHash, BlockNumber, Bloom, Bytes, Client, HexBytes, RPCError, Result, TransactionHash, TypeExtensions, ACTION,
+    ADDRESS, MODULE, U256,
 };
-use crate::{Tag, TAG};
+use crate::{BlockId, TAG};
 use async_trait::async_trait;
-use ethabi::ethereum_types::U64;
+use chrono::{DateTime, TimeZone, Utc};
+use ethabi::ethereum_types::{H256, H64, U64};
 use serde::de::DeserializeOwned;
 use serde::{
     de,
     de::{MapAccess, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
-use serde_with::{serde_as, DisplayFromStr};
+use serde_with::serde_as;
 use std::fmt;
 use std::marker::PhantomData;
 
+pub(crate) use revert::revert_data;
+pub use revert::Revert;
+
+mod revert;
 #[cfg(test)]
 mod tests;
 
@@ -46,16 +52,16 @@ pub trait Proxy {
     ///
     /// * 'contract_address' - The contract address to interact with
     /// * 'data' - The hash of the method signature and encoded parameters
-    /// * 'tag' - The pre-defined block parameter, which defaults to latest if not provided.
-    async fn call(&self, contract_address: &Address, data: &str, tag: Option<Tag>) -> Result<String>;
+    /// * 'block' - The block to call at, by number or tag, which defaults to latest if not provided.
+    async fn call(&self, contract_address: &Address, data: &str, block: Option<BlockId>) -> Result<String>;
 
     /// Returns code at a given address
     ///
     /// # Arguments
     ///
     /// * 'address' - The address to get code
-    /// * 'tag' - The pre-defined block parameter
-    async fn code(&self, address: &Address, tag: Option<Tag>) -> Result<String>;
+    /// * 'block' - The block to get the code at, by number or tag, which defaults to latest if not provided.
+    async fn code(&self, address: &Address, block: Option<BlockId>) -> Result<String>;
 
     /// Makes a call or transaction, which won't be added to the blockchain, but returns the used gas
     ///
@@ -63,15 +69,15 @@ pub trait Proxy {
     ///
     /// * 'contract_address' - The contract address to interact with
     /// * 'data' - the hash of the method signature and encoded parameters
-    /// * 'value' - the value sent in this transaction, in hex
-    /// * 'gas' - the amount of gas provided for the transaction, in hex
+    /// * 'value' - the value sent in this transaction, in wei
+    /// * 'gas' - the amount of gas provided for the transaction
     /// * 'gas_price' - the gas price paid for each unit of gas, in wei
     ///
     /// **Note:** The gas parameter is capped at 2x the current block gas limit.
-    async fn estimate_gas(&self, contract_address: &Address, data: &str, value: u64, gas: u64, gas_price: u64) -> Result<u64>;
+    async fn estimate_gas(&self, contract_address: &Address, data: &str, value: U256, gas: u64, gas_price: U256) -> Result<u64>;
 
     /// Returns the current price per gas in wei
-    async fn gas_price(&self) -> Result<u64>;
+    async fn gas_price(&self) -> Result<U256>;
 
     /// Submits a pre-signed transaction for broadcast to the Ethereum network
     ///
@@ -85,11 +91,11 @@ pub trait Proxy {
     /// # Arguments
     ///
     /// * 'address' - The address to get code
-    /// * 'position' - The position in storage
-    /// * 'tag' - The pre-defined block parameter
+    /// * 'slot' - The storage slot, such as computed for mapping entries or array elements with [`slots`](crate::slots)
+    /// * 'block' - The block to get the value at, by number or tag, which defaults to latest if not provided.
     ///
     /// **Note:** This endpoint is still experimental and may have potential issues
-    async fn storage_value(&self, address: &Address, position: u16, tag: Option<Tag>) -> Result<String>;
+    async fn storage_value(&self, address: &Address, slot: H256, block: Option<BlockId>) -> Result<H256>;
 
     /// Returns the information about a transaction requested by transaction hash
     ///
@@ -118,8 +124,8 @@ pub trait Proxy {
     /// # Arguments
     ///
     /// * 'address' - The address to get the number of transactions performed
-    /// * 'tag' - The pre-defined block parameter, which defaults to latest if not provided.
-    async fn transactions(&self, address: &Address, tag: Option<Tag>) -> Result<u64>;
+    /// * 'block' - The block to count transactions up to, by number or tag, which defaults to latest if not provided.
+    async fn transactions(&self, address: &Address, block: Option<BlockId>) -> Result<u64>;
 
     /// Returns information about a uncle by block number.
     ///
@@ -157,28 +163,28 @@ impl Proxy for Client {
         self.get_json_rpc::<U64>(parameters).await.map(|t| t.as_u64())
     }
 
-    async fn call(&self, contract_address: &Address, data: &str, tag: Option<Tag>) -> Result<String> {
+    async fn call(&self, contract_address: &Address, data: &str, block: Option<BlockId>) -> Result<String> {
         let parameters = &[
             (MODULE, PROXY),
             (ACTION, "eth_call"),
             ("to", &TypeExtensions::format(contract_address)),
             ("data", data),
-            (TAG, tag.or(Some(Tag::Latest)).unwrap().to_string()),
+            (TAG, &block_tag(block)?),
         ];
         self.get_json_rpc(parameters).await
     }
 
-    async fn code(&self, address: &Address, tag: Option<Tag>) -> Result<String> {
+    async fn code(&self, address: &Address, block: Option<BlockId>) -> Result<String> {
         let parameters = &[
             (MODULE, PROXY),
             (ACTION, "eth_getCode"),
             (ADDRESS, &TypeExtensions::format(address)),
-            (TAG, tag.or(Some(Tag::Latest)).unwrap().to_string()),
+            (TAG, &block_tag(block)?),
         ];
         self.get_json_rpc(parameters).await
     }
 
-    async fn estimate_gas(&self, contract_address: &Address, data: &str, value: u64, gas: u64, gas_price: u64) -> Result<u64> {
+    async fn estimate_gas(&self, contract_address: &Address, data: &str, value: U256, gas: u64, gas_price: U256) -> Result<u64> {
         let parameters = &[
             (MODULE, PROXY),
             (ACTION, "eth_estimateGas"),
@@ -191,9 +197,9 @@ impl Proxy for Client {
         self.get_json_rpc::<U64>(parameters).await.map(|t| t.as_u64())
     }
 
-    async fn gas_price(&self) -> Result<u64> {
+    async fn gas_price(&self) -> Result<U256> {
         let parameters = &[(MODULE, PROXY), (ACTION, "eth_gasPrice")];
-        self.get_json_rpc::<U64>(parameters).await.map(|t| t.as_u64())
+        self.get_json_rpc(parameters).await
     }
 
     async fn send_transaction(&self, transaction: String) -> Result<TransactionHash> {
@@ -201,13 +207,13 @@ impl Proxy for Client {
         self.get_json_rpc(parameters).await
     }
 
-    async fn storage_value(&self, address: &Address, position: u16, tag: Option<Tag>) -> Result<String> {
+    async fn storage_value(&self, address: &Address, slot: H256, block: Option<BlockId>) -> Result<H256> {
         let parameters = &[
             (MODULE, PROXY),
             (ACTION, "eth_getStorageAt"),
             (ADDRESS, &TypeExtensions::format(address)),
-            ("position", &TypeExtensions::format(&position)),
-            (TAG, tag.or(Some(Tag::Latest)).unwrap().to_string()),
+            ("position", &TypeExtensions::format(&slot)),
+            (TAG, &block_tag(block)?),
         ];
         self.get_json_rpc(parameters).await
     }
@@ -240,12 +246,12 @@ impl Proxy for Client {
         self.get_json_rpc(parameters).await
     }
 
-    async fn transactions(&self, address: &Address, tag: Option<Tag>) -> Result<u64> {
+    async fn transactions(&self, address: &Address, block: Option<BlockId>) -> Result<u64> {
         let parameters = &[
             (MODULE, PROXY),
             (ACTION, "eth_getTransactionCount"),
             (ADDRESS, &TypeExtensions::format(address)),
-            (TAG, tag.or(Some(Tag::Latest)).unwrap().to_string()),
+            (TAG, &block_tag(block)?),
         ];
         self.get_json_rpc::<U64>(parameters).await.map(|t| t.as_u64())
     }
@@ -262,57 +268,119 @@ impl Proxy for Client {
 }
 
 impl Client {
-    async fn get_json_rpc<'de, T: DeserializeOwned>(&self, parameters: &[(&str, &str)]) -> Result<T> {
-        self.client
-            .get(URI)
-            .query(&[("apikey", &self.api_key)])
-            .query(parameters)
-            .send()
-            .await?
-            .json::<Response<T>>()
+    async fn get_json_rpc<T: DeserializeOwned>(&self, parameters: &[(&str, &str)]) -> Result<T> {
+        self.fetch(parameters, |body| serde_json::from_str::<Response<T>>(body).map(|r| r.result))
             .await
-            .map(|r| r.result)
-            .map_err(|e| APIError::from(e))
+    }
+
+    /// Returns the balance of an address in wei at a block, such as the latest finalized block.
+    ///
+    /// # Arguments
+    ///
+    /// * 'address' - An address
+    /// * 'block' - The block, by number or tag
+    pub(crate) async fn balance_at(&self, address: &Address, block: BlockId) -> Result<U256> {
+        let parameters = &[
+            (MODULE, PROXY),
+            (ACTION, "eth_getBalance"),
+            (ADDRESS, &TypeExtensions::format(address)),
+            (TAG, &block_tag(Some(block))?),
+        ];
+        self.get_json_rpc(parameters).await
+    }
+}
+
+/// Returns the block as the tag parameter of a proxy request, which defaults to latest if not provided.
+///
+/// Blocks by hash are not supported, as the API only accepts a number or a tag.
+fn block_tag(block: Option<BlockId>) -> Result<String> {
+    match block.unwrap_or(BlockId::Latest) {
+        block @ BlockId::Hash(_) => Err(APIError::UnsupportedBlock { block }),
+        block => Ok(block.to_string()),
     }
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Block {
-    pub base_fee_per_gas: String,
-    pub difficulty: String,
-    pub extra_data: String,
-    pub gas_limit: String,
-    pub gas_used: String,
+    /// The base fee per gas in wei (none before the London fork)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    pub difficulty: U256,
+    #[serde_as(as = "HexBytes")]
+    pub extra_data: Bytes,
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub gas_limit: u64,
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub gas_used: u64,
     pub hash: BlockHash,
-    pub logs_bloom: String,
-    pub miner: String,
-    pub mix_hash: String,
-    pub nonce: String,
-    #[serde_as(as = "DisplayFromStr")]
+    pub logs_bloom: Bloom,
+    pub miner: Address,
+    pub mix_hash: H256,
+    pub nonce: H64,
     pub number: BlockNumber,
     pub parent_hash: Option<BlockHash>,
-    pub receipts_root: String,
-    pub sha3_uncles: String,
-    pub size: String,
-    pub state_root: String,
-    pub timestamp: String,
-    pub total_difficulty: Option<String>,
-    pub transactions: Option<Vec<String>>,
-    pub transactions_root: String,
-    pub uncles: Vec<String>,
+    pub receipts_root: H256,
+    pub sha3_uncles: H256,
+    /// The size of the block in bytes
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub size: u64,
+    pub state_root: H256,
+    #[serde(deserialize_with = "de_hash_to_timestamp", serialize_with = "se_timestamp_to_hash")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_difficulty: Option<U256>,
+    pub transactions: Option<Vec<TransactionHash>>,
+    pub transactions_root: H256,
+    pub uncles: Vec<BlockHash>,
+    /// The withdrawals from the beacon chain (none before the Shanghai fork)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
+    /// The total blob gas used by the transactions in the block (none before the Cancun fork)
+    #[serde(
+        default,
+        deserialize_with = "de_hash_to_optional_u64",
+        serialize_with = "se_optional_to_hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_used: Option<u64>,
+    #[serde(
+        default,
+        deserialize_with = "de_hash_to_optional_u64",
+        serialize_with = "se_optional_to_hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub excess_blob_gas: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<H256>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub index: u64,
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub validator_index: u64,
+    pub address: Address,
+    /// The amount withdrawn in gwei
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub amount: u64,
 }
 
 struct Response<T> {
     #[allow(dead_code)]
-    pub id: u32,
+    pub id: Option<u32>,
     #[allow(dead_code)]
-    pub json_rpc: String,
-    pub result: T,
+    pub json_rpc: Option<String>,
+    pub result: std::result::Result<T, Failure>,
 }
 
-#[derive(Debug, Deserialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     /// The hash of the block where this transaction was in (none if pending)
@@ -322,45 +390,116 @@ pub struct Transaction {
     /// Address of the sender
     pub from: Address,
     /// The gas provided by the sender,
-    #[serde(deserialize_with = "de_hash_to_u64")]
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
     pub gas: u64,
-    /// The gas price provided by the sender in Wei
-    #[serde(deserialize_with = "de_hash_to_u64")]
-    pub gas_price: u64,
-    #[serde(deserialize_with = "de_hash_to_u64")]
-    pub max_fee_per_gas: u64,
-    #[serde(deserialize_with = "de_hash_to_u64")]
-    pub max_priority_fee_per_gas: u64,
+    /// The gas price provided by the sender in Wei, or the effective gas price for EIP-1559 and blob transactions
+    pub gas_price: U256,
+    /// The maximum fee per gas in Wei (EIP-1559 and blob transactions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    /// The maximum priority fee per gas in Wei (EIP-1559 and blob transactions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// The maximum fee per blob gas in Wei (blob transactions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
     /// The hash of the transaction
     pub hash: TransactionHash,
     /// The data sent along with the transaction.
-    pub input: String,
+    #[serde_as(as = "HexBytes")]
+    pub input: Bytes,
     /// The number of transactions made by the sender prior to this one
-    #[serde(deserialize_with = "de_hash_to_u64")]
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
     pub nonce: u64,
     /// Address of the receiver (none when its a contract creation transaction)
     pub to: Option<Address>,
     /// The transaction's index position in the block (none if pending)
-    #[serde(deserialize_with = "de_hash_to_optional_u32")]
-    pub transaction_index: Option<u32>,
+    #[serde(default, deserialize_with = "de_hash_to_optional_u64", serialize_with = "se_optional_to_hash")]
+    pub transaction_index: Option<u64>,
     /// The value transferred in Wei
-    #[serde(deserialize_with = "de_hash_to_u64")]
-    pub value: u64,
-    #[serde(rename = "type")]
-    #[serde(deserialize_with = "de_hash_to_u8")]
-    pub transaction_type: u8,
-    //pub access_list
-    // The chain id of the transaction, if any.
-    #[serde(deserialize_with = "de_hash_to_optional_u8")]
-    pub chain_id: Option<u8>,
+    pub value: U256,
+    /// The type of the transaction, which is legacy if not given
+    #[serde(
+        rename = "type",
+        default,
+        deserialize_with = "de_transaction_type",
+        serialize_with = "se_transaction_type"
+    )]
+    pub transaction_type: TransactionType,
+    /// The addresses and storage keys the transaction plans to access (all but legacy transactions)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<AccessListItem>>,
+    /// The versioned hashes of the blobs of the transaction (blob transactions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    /// The chain id of the transaction, if any.
+    #[serde(
+        default,
+        deserialize_with = "de_hash_to_optional_u64",
+        serialize_with = "se_optional_to_hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub chain_id: Option<u64>,
     /// The standardized V field of the signature
-    pub v: String,
+    pub v: U64,
+    /// The parity of the y coordinate of the signature (all but legacy transactions)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y_parity: Option<U64>,
     /// The R field of the signature
-    pub r: String,
-    pub s: String,
+    pub r: U256,
+    /// The S field of the signature
+    pub s: U256,
+}
+
+/// The type of a transaction, which determines its fee and access fields.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransactionType {
+    /// A legacy transaction, with a gas price
+    #[default]
+    Legacy,
+    /// An EIP-2930 transaction, with an access list
+    AccessList,
+    /// An EIP-1559 transaction, with a maximum fee and priority fee per gas
+    DynamicFee,
+    /// An EIP-4844 transaction, carrying blobs
+    Blob,
+    /// Another type of transaction, such as those specific to a layer 2 network
+    Other(u8),
+}
+
+impl From<u8> for TransactionType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => TransactionType::Legacy,
+            1 => TransactionType::AccessList,
+            2 => TransactionType::DynamicFee,
+            3 => TransactionType::Blob,
+            value => TransactionType::Other(value),
+        }
+    }
+}
+
+impl From<TransactionType> for u8 {
+    fn from(value: TransactionType) -> Self {
+        match value {
+            TransactionType::Legacy => 0,
+            TransactionType::AccessList => 1,
+            TransactionType::DynamicFee => 2,
+            TransactionType::Blob => 3,
+            TransactionType::Other(value) => value,
+        }
+    }
+}
+
+/// An address and the storage keys a transaction plans to access within it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<H256>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionReceipt {
     /// Hash of the block where this transaction was in
@@ -370,46 +509,87 @@ pub struct TransactionReceipt {
     /// The contract address created for contract creation, otherwise none
     pub contract_address: Option<Address>,
     /// The total gas used when this transaction was executed in the block
-    #[serde(deserialize_with = "de_hash_to_u64")]
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
     pub cumulative_gas_used: u64,
-    #[serde(deserialize_with = "de_hash_to_u64")]
-    pub effective_gas_price: u64,
+    pub effective_gas_price: U256,
     /// Address of the sender
     pub from: Address,
     /// The amount of gas used by this specific transaction alone
-    #[serde(deserialize_with = "de_hash_to_u64")]
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
     pub gas_used: u64,
+    /// The blob gas used by this transaction (blob transactions only)
+    #[serde(
+        default,
+        deserialize_with = "de_hash_to_optional_u64",
+        serialize_with = "se_optional_to_hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_used: Option<u64>,
+    /// The price per unit of blob gas paid in Wei (blob transactions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_price: Option<U256>,
     /// Array of log entries, which this transaction generated
     pub logs: Vec<LogEntry>,
     /// Bloom filter for light clients to quickly retrieve related logs
-    pub logs_bloom: String,
-    #[serde(deserialize_with = "de_hash_to_u8")]
-    pub status: u8,
+    pub logs_bloom: Bloom,
+    /// Whether the transaction succeeded, or the state root for transactions before the Byzantium fork
+    #[serde(flatten, deserialize_with = "de_receipt_status", serialize_with = "se_receipt_status")]
+    pub status: ReceiptStatus,
     /// Address of the receiver (none when its a contract creation transaction)
     pub to: Option<Address>,
     /// Hash of the transaction
     pub transaction_hash: TransactionHash,
-    #[serde(deserialize_with = "de_hash_to_u16")]
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
     /// Integer of the transactions index position in the block
-    pub transaction_index: u16,
-    #[serde(rename = "type")]
-    #[serde(deserialize_with = "de_hash_to_u8")]
-    pub transaction_type: u8,
+    pub transaction_index: u64,
+    /// The type of the transaction, which is legacy if not given
+    #[serde(
+        rename = "type",
+        default,
+        deserialize_with = "de_transaction_type",
+        serialize_with = "se_transaction_type"
+    )]
+    pub transaction_type: TransactionType,
 }
 
-#[derive(Debug, Deserialize)]
+impl TransactionReceipt {
+    /// Returns the fee paid for the transaction in Wei, including the blob fee of blob transactions.
+    pub fn fee(&self) -> U256 {
+        let execution = self.effective_gas_price.saturating_mul(U256::from(self.gas_used));
+        let blob = match (self.blob_gas_used, self.blob_gas_price) {
+            (Some(used), Some(price)) => price.saturating_mul(U256::from(used)),
+            _ => U256::zero(),
+        };
+        execution.saturating_add(blob)
+    }
+}
+
+/// The outcome of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiptStatus {
+    /// The transaction succeeded
+    Success,
+    /// The transaction failed, such as by reverting
+    Failed,
+    /// The transaction was executed before the Byzantium fork, so only the resulting state root is known
+    PreByzantium(H256),
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LogEntry {
     pub address: Address,
-    pub topics: Vec<String>,
-    pub data: String,
+    pub topics: Vec<H256>,
+    #[serde_as(as = "HexBytes")]
+    pub data: Bytes,
     pub block_number: BlockNumber,
     pub transaction_hash: TransactionHash,
-    #[serde(deserialize_with = "de_hash_to_u16")]
-    pub transaction_index: u16,
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub transaction_index: u64,
     pub block_hash: BlockHash,
-    #[serde(deserialize_with = "de_hash_to_u16")]
-    pub log_index: u16,
+    #[serde(deserialize_with = "de_hash_to_u64", serialize_with = "se_to_hash")]
+    pub log_index: u64,
     pub removed: bool,
 }
 
@@ -434,11 +614,12 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Response<T> {
             fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> std::result::Result<Response<T>, V::Error> {
                 let mut id = None;
                 let mut json_rpc = None;
-                let mut result = None;
+                let mut error = None;
+                let mut result: Option<serde_json::Value> = None;
                 let mut status = None;
                 let mut message: Option<String> = None;
-                while let Some(key) = map.next_key()? {
-                    match key {
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
                         ID => {
                             if id.is_some() {
                                 return Err(de::Error::duplicate_field(ID));
@@ -452,101 +633,151 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Response<T> {
                             json_rpc = Some(map.next_value::<String>()?);
                         }
                         ERROR => {
-                            let error = map.next_value::<RPCError>()?;
-                            return Err(de::Error::custom(format!(
-                                "rpc error:{}",
-                                serde_json::to_string(&error).expect("could not serialize error")
-                            )));
+                            if error.is_some() {
+                                return Err(de::Error::duplicate_field(ERROR));
+                            }
+                            error = Some(map.next_value::<RPCError>()?);
                         }
                         STATUS => {
                             if status.is_some() {
                                 return Err(de::Error::duplicate_field(STATUS));
                             }
-                            let value: String = map.next_value()?;
-                            status = Some(match value.as_str() {
-                                "1" => ResponseStatus::Success,
-                                "0" => ResponseStatus::Failed,
-                                _ => panic!("Unknown value: {}", value),
-                            });
+                            status = Some(map.next_value::<ResponseStatus>()?);
                         }
                         MESSAGE => {
                             if message.is_some() {
                                 return Err(de::Error::duplicate_field(MESSAGE));
                             }
                             message = Some(map.next_value()?);
-
-                            if let Some(ResponseStatus::Failed) = status {
-                                if let Some(message) = { &message } {
-                                    if message == "NOTOK" {
-                                        // Get message from next value
-                                        let next: Option<(String, String)> = map.next_entry()?;
-                                        if let Some((_, value)) = next {
-                                            return Err(de::Error::custom(value));
-                                        }
-                                    }
-                                }
-                            }
                         }
                         RESULT => {
                             if result.is_some() {
                                 return Err(de::Error::duplicate_field(RESULT));
                             }
-
-                            // Return result message as error if failed
-                            if let Some(ResponseStatus::Failed) = status {
-                                if let Some(message) = { &message } {
-                                    // Exclude empty result, which returns as status failed
-                                    if message != "No transactions found" {
-                                        let value: String = map.next_value()?;
-                                        return Err(de::Error::custom(value));
-                                    }
-                                }
-                            }
-
                             result = Some(map.next_value()?);
                         }
                         _ => {
                             // Ignore value
-                            let _ = map.next_value::<()>();
+                            map.next_value::<de::IgnoredAny>()?;
                         }
                     }
                 }
+
+                // Return any failure in place of the result
+                if let Some(error) = error {
+                    return Ok(Response {
+                        id,
+                        json_rpc,
+                        result: Err(Failure::Rpc {
+                            code: error.code,
+                            message: error.message,
+                            data: error.data,
+                        }),
+                    });
+                }
+                if let Some(ResponseStatus::Failed) = status {
+                    // Exclude empty result, which returns as status failed
+                    let result = match message.as_deref().and_then(empty_result::<T>) {
+                        Some(empty) => Ok(empty),
+                        None => Err(Failure::Api {
+                            message: message.ok_or_else(|| de::Error::missing_field(MESSAGE))?,
+                            result: result.unwrap_or_default(),
+                        }),
+                    };
+                    return Ok(Response { id, json_rpc, result });
+                }
+
                 let id = id.ok_or_else(|| de::Error::missing_field(ID))?;
                 let json_rpc = json_rpc.ok_or_else(|| de::Error::missing_field(JSON_RPC))?;
                 let result = result.ok_or_else(|| de::Error::missing_field(RESULT))?;
-                Ok(Response::<T> { id, json_rpc, result })
+                T::deserialize(result)
+                    .map(|result| Response {
+                        id: Some(id),
+                        json_rpc: Some(json_rpc),
+                        result: Ok(result),
+                    })
+                    .map_err(de::Error::custom)
             }
         }
 
-        const FIELDS: &[&str] = &[ID, JSON_RPC, STATUS, MESSAGE, RESULT];
+        const FIELDS: &[&str] = &[ID, JSON_RPC, ERROR, STATUS, MESSAGE, RESULT];
         deserializer.deserialize_struct("Response", FIELDS, ResultVisitor(PhantomData))
     }
 }
 
 fn de_hash_to_u8<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<u8, D::Error> {
-    U64::deserialize(deserializer).map(|v| v.as_u32() as u8)
+    de_hash_to_u64(deserializer).and_then(|v| u8::try_from(v).map_err(de::Error::custom))
 }
 
-fn de_hash_to_optional_u8<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<Option<u8>, D::Error> {
-    U64::deserialize(deserializer).map(|v| Some(v.as_u32() as u8))
-}
+fn de_receipt_status<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<ReceiptStatus, D::Error> {
+    #[derive(Deserialize)]
+    struct Status {
+        #[serde(default, deserialize_with = "de_hash_to_optional_u64")]
+        status: Option<u64>,
+        #[serde(default)]
+        root: Option<H256>,
+    }
 
-fn de_hash_to_u16<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<u16, D::Error> {
-    U64::deserialize(deserializer).map(|v| v.as_u32() as u16)
+    match Status::deserialize(deserializer)? {
+        Status { status: Some(1), .. } => Ok(ReceiptStatus::Success),
+        Status { status: Some(0), .. } => Ok(ReceiptStatus::Failed),
+        Status { status: Some(status), .. } => Err(de::Error::custom(format!("invalid receipt status {}", status))),
+        Status { root: Some(root), .. } => Ok(ReceiptStatus::PreByzantium(root)),
+        Status { .. } => Err(de::Error::missing_field("status")),
+    }
 }
 
-// fn de_hash_to_u32<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<u32, D::Error> {
-//     U64::deserialize(deserializer).map(|v| v.as_u32())
-// }
-
-fn de_hash_to_optional_u32<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<Option<u32>, D::Error> {
-    U64::deserialize(deserializer).map(|v| Some(v.as_u32()))
+fn de_transaction_type<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<TransactionType, D::Error> {
+    de_hash_to_u8(deserializer).map(TransactionType::from)
 }
 
 fn de_hash_to_u64<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<u64, D::Error> {
     U64::deserialize(deserializer).map(|v| v.as_u64())
 }
 
-// fn de_hash_to_u128<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<u128, D::Error> {
-//     U128::deserialize(deserializer).map(|v| v.as_u128())
-// }
+fn de_hash_to_optional_u64<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<Option<u64>, D::Error> {
+    Option::<U64>::deserialize(deserializer).map(|v| v.map(|v| v.as_u64()))
+}
+
+fn de_hash_to_timestamp<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<DateTime<Utc>, D::Error> {
+    let seconds = de_hash_to_u64(deserializer)?;
+    i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single())
+        .ok_or_else(|| de::Error::custom(format!("invalid timestamp {}", seconds)))
+}
+
+fn se_to_hash<T: Copy + Into<u64>, S: Serializer>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    U64::from((*value).into()).serialize(serializer)
+}
+
+fn se_optional_to_hash<T: Copy + Into<u64>, S: Serializer>(value: &Option<T>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    value.map(|v| U64::from(v.into())).serialize(serializer)
+}
+
+fn se_timestamp_to_hash<S: Serializer>(value: &DateTime<Utc>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    let seconds =
+        u64::try_from(value.timestamp()).map_err(|_| serde::ser::Error::custom(format!("invalid timestamp {}", value.timestamp())))?;
+    U64::from(seconds).serialize(serializer)
+}
+
+fn se_transaction_type<S: Serializer>(value: &TransactionType, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    se_to_hash(&u8::from(*value), serializer)
+}
+
+fn se_receipt_status<S: Serializer>(value: &ReceiptStatus, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Status {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<U64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        root: Option<H256>,
+    }
+
+    let (status, root) = match value {
+        ReceiptStatus::Success => (Some(U64::one()), None),
+        ReceiptStatus::Failed => (Some(U64::zero()), None),
+        ReceiptStatus::PreByzantium(root) => (None, Some(*root)),
+    };
+    Status { status, root }.serialize(serializer)
+}
@@ -0,0 +1,103 @@
+use crate::contracts::{Token, ABI};
+use ethabi::ethereum_types::U256;
+use ethabi::ParamType;
+use std::fmt;
+
+/// The selector of `Error(string)`, used by `require(condition, "reason")` and `revert("reason")`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// The selector of `Panic(uint256)`, used by failed assertions, arithmetic overflow and similar.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// The reason a call reverted, decoded from the revert data returned with its JSON-RPC error.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Revert {
+    /// A reason, from `require(condition, "reason")` or `revert("reason")`
+    Error(String),
+    /// A panic code, such as 0x01 for a failed assertion or 0x11 for arithmetic overflow
+    Panic(U256),
+    /// A custom Solidity error, decoded using the ABI of the contract
+    Custom { name: String, parameters: Vec<Token> },
+    /// Revert data which could not be decoded, such as a custom error without the ABI of the contract
+    Raw(Vec<u8>),
+}
+
+impl Revert {
+    /// Decodes revert data, recognising `Error(string)` and `Panic(uint256)`.
+    ///
+    /// # Arguments
+    ///
+    /// * 'data' - The revert data
+    pub fn decode(data: &[u8]) -> Revert {
+        let (selector, parameters) = match split_selector(data) {
+            Some(split) => split,
+            None => return Revert::Raw(data.to_vec()),
+        };
+        let decoded = match selector {
+            ERROR_SELECTOR => ethabi::decode(&[ParamType::String], parameters)
+                .ok()
+                .and_then(|tokens| tokens.into_iter().next()?.into_string())
+                .map(Revert::Error),
+            PANIC_SELECTOR => ethabi::decode(&[ParamType::Uint(256)], parameters)
+                .ok()
+                .and_then(|tokens| tokens.into_iter().next()?.into_uint())
+                .map(Revert::Panic),
+            _ => None,
+        };
+        decoded.unwrap_or_else(|| Revert::Raw(data.to_vec()))
+    }
+
+    /// Decodes revert data, recognising the custom errors of a contract as well as `Error(string)` and
+    /// `Panic(uint256)`.
+    ///
+    /// # Arguments
+    ///
+    /// * 'data' - The revert data
+    /// * 'abi' - The ABI of the contract called, such as from [`Contracts::get_abi`](crate::contracts::Contracts::get_abi)
+    pub fn decode_with(data: &[u8], abi: &ABI) -> Revert {
+        if let Some((selector, parameters)) = split_selector(data) {
+            for error in abi.errors() {
+                if error.signature()[..4] == selector[..] {
+                    if let Ok(parameters) = error.decode(parameters) {
+                        return Revert::Custom {
+                            name: error.name.clone(),
+                            parameters,
+                        };
+                    }
+                }
+            }
+        }
+        Revert::decode(data)
+    }
+}
+
+impl fmt::Display for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Revert::Error(reason) => write!(f, "reverted: {}", reason),
+            Revert::Panic(code) => write!(f, "panicked with code {:#x}", code),
+            Revert::Custom { name, parameters } => {
+                let parameters = parameters.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+                write!(f, "reverted with {}({})", name, parameters.join(", "))
+            }
+            Revert::Raw(data) => write!(f, "reverted with 0x{}", hex::encode(data)),
+        }
+    }
+}
+
+/// Returns the revert data within the data of a JSON-RPC error, which is either a hex string or (for some nodes) an
+/// object holding it.
+pub(crate) fn revert_data(data: &serde_json::Value) -> Option<Vec<u8>> {
+    match data {
+        serde_json::Value::String(data) => hex::decode(data.strip_prefix("0x").unwrap_or(data)).ok(),
+        serde_json::Value::Object(object) => object.get("data").and_then(revert_data),
+        _ => None,
+    }
+}
+
+/// Splits revert data into its 4 byte selector and the encoded parameters which follow it (if long enough).
+fn split_selector(data: &[u8]) -> Option<([u8; 4], &[u8])> {
+    match data {
+        [a, b, c, d, parameters @ ..] => Some(([*a, *b, *c, *d], parameters)),
+        _ => None,
+    }
+}
@@ -1,13 +1,19 @@
-use super::Client;
+use super::{Block, Client, ReceiptStatus, Response, Revert, Transaction, TransactionReceipt, TransactionType};
+use crate::contracts::ABI;
 use crate::proxy::Proxy;
-use crate::{Address, BlockNumber, Tag, TransactionHash};
+use crate::tests::{response, round_trip, FakeTransport};
+use crate::{slots, APIError, BloomExtensions};
+use crate::{Address, BlockId, BlockNumber, TransactionHash};
+use ethabi::ethereum_types::{Bloom, BloomInput, H256, U256, U64};
+use ethabi::{ParamType, Token};
 use once_cell::sync::Lazy;
+use proptest::prelude::*;
 use std::str::FromStr;
 
 const API_KEY: &str = "";
 const BLOCK_NUMBER: &str = "0xc63251";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn block_number() -> Result<(), crate::APIError> {
@@ -43,7 +49,7 @@ async fn call() -> Result<(), crate::APIError> {
         .call(
             &address,
             "0x70a08231000000000000000000000000e16359506c028e51f16be38986ec5746251e9724",
-            Some(Tag::Latest),
+            Some(BlockId::Latest),
         )
         .await?;
     assert_eq!("0x00000000000000000000000000000000000000000000000000601d8888141c00", result);
@@ -65,9 +71,9 @@ async fn code() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn estimate_gas() -> Result<(), crate::APIError> {
     let address = Address::from_str("0xf0160428a8552ac9bb7e050d90eeade4ddd52843").expect("could not parse address");
-    let value = 65314;
+    let value = U256::from(65314);
     let gas = 99999999;
-    let gas_price = 56478107993;
+    let gas_price = U256::from(56478107993u64);
     let gas = CLIENT.estimate_gas(&address, "0x4e71d92d", value, gas, gas_price).await?;
     assert_ne!(0, gas);
     println!("Estimated gas is {gas}");
@@ -77,7 +83,7 @@ async fn estimate_gas() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn gas_price() -> Result<(), crate::APIError> {
     let gas_price = CLIENT.gas_price().await?;
-    assert_ne!(0, gas_price);
+    assert!(!gas_price.is_zero());
     println!("Current gas price is {gas_price}");
     Ok(())
 }
@@ -85,9 +91,8 @@ async fn gas_price() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn storage_value() -> Result<(), crate::APIError> {
     let address = Address::from_str("0x6e03d9cce9d60f3e9f2597e13cd4c54c55330cfd").expect("could not parse as address");
-    let position = 0;
-    let value = CLIENT.storage_value(&address, position, None).await?;
-    assert_eq!("0x0000000000000000000000000000000000000000000000000000000000000000", value);
+    let value = CLIENT.storage_value(&address, slots::word(0), None).await?;
+    assert_eq!(H256::zero(), value);
     Ok(())
 }
 
@@ -135,7 +140,7 @@ async fn transaction_within_block() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn transactions() -> Result<(), crate::APIError> {
     let address = Address::from_str("0x4bd5900Cb274ef15b153066D736bf3e83A9ba44e").expect("could not parse address");
-    let transactions = CLIENT.transactions(&address, Some(Tag::Latest)).await?;
+    let transactions = CLIENT.transactions(&address, Some(BlockId::Latest)).await?;
     assert_eq!(113, transactions);
     println!("Transactions for {address} is {transactions}");
     Ok(())
@@ -149,3 +154,405 @@ async fn uncle() -> Result<(), crate::APIError> {
     println!("Uncle information for {uncle} and {INDEX} is \n{:#?}", block);
     Ok(())
 }
+
+#[test]
+fn response_unknown_status() {
+    let response = serde_json::from_str::<Response<String>>(r#"{"status":"OK","message":"OK","result":"0x1"}"#);
+    assert!(response.is_err());
+}
+
+#[test]
+fn response_overflow() {
+    let response = serde_json::from_str::<Response<TransactionReceipt>>(
+        r#"{"jsonrpc":"2.0","id":1,"result":{"transactionIndex":"0xffffffffffffffff"}}"#,
+    );
+    assert!(response.is_err());
+}
+
+/// Returns a block response, with the given fields in addition to those of every block.
+fn block_response(fields: &str) -> String {
+    let hash = format!("0x{}", "11".repeat(32));
+    format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"difficulty":"0x2","extraData":"0xd883","gasLimit":"0x1c9c380","gasUsed":"0x5208","hash":"{hash}","logsBloom":"0x{}","miner":"0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5","mixHash":"{hash}","nonce":"0x0000000000000042","number":"0x12a05f2","parentHash":"{hash}","receiptsRoot":"{hash}","sha3Uncles":"{hash}","size":"0x220","stateRoot":"{hash}","timestamp":"0x65f1b057","transactions":["{hash}"],"transactionsRoot":"{hash}","uncles":[]{fields}}}}}"#,
+        "00".repeat(256)
+    )
+}
+
+#[test]
+fn block_pre_london() {
+    let block = serde_json::from_str::<Response<Block>>(&block_response(r#","totalDifficulty":"0x3""#))
+        .expect("could not parse block")
+        .result
+        .expect("expected block");
+    assert_eq!(None, block.base_fee_per_gas);
+    assert!(block.withdrawals.is_none());
+    assert_eq!(None, block.blob_gas_used);
+    assert_eq!(U256::from(2), block.difficulty);
+    assert_eq!(Some(U256::from(3)), block.total_difficulty);
+    assert_eq!(30_000_000, block.gas_limit);
+    assert_eq!(21_000, block.gas_used);
+    assert_eq!(vec![0xd8, 0x83], block.extra_data);
+    assert_eq!(544, block.size);
+    assert_eq!(BlockNumber::from(19_531_250), block.number);
+    assert_eq!("2024-03-13T13:55:35+00:00", block.timestamp.to_rfc3339());
+    assert_eq!(
+        Address::from_str("0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5").expect("could not parse address"),
+        block.miner
+    );
+}
+
+#[test]
+fn block_cancun() {
+    let root = format!("0x{}", "22".repeat(32));
+    let block = serde_json::from_str::<Response<Block>>(&block_response(&format!(
+        r#","baseFeePerGas":"0x3b9aca00","withdrawals":[{{"index":"0x2a","validatorIndex":"0x10","address":"0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5","amount":"0x11e1a300"}}],"withdrawalsRoot":"{root}","blobGasUsed":"0x20000","excessBlobGas":"0x0","parentBeaconBlockRoot":"{root}""#
+    )))
+    .expect("could not parse block")
+    .result
+    .expect("expected block");
+    assert_eq!(Some(U256::from(1_000_000_000)), block.base_fee_per_gas);
+    let withdrawals = block.withdrawals.expect("expected withdrawals");
+    assert_eq!(42, withdrawals[0].index);
+    assert_eq!(16, withdrawals[0].validator_index);
+    assert_eq!(300_000_000, withdrawals[0].amount);
+    assert!(block.withdrawals_root.is_some());
+    assert_eq!(Some(131_072), block.blob_gas_used);
+    assert_eq!(Some(0), block.excess_blob_gas);
+    assert_eq!(block.withdrawals_root, block.parent_beacon_block_root);
+}
+
+/// Returns a transaction response, with the given fields in addition to those of every transaction.
+fn transaction_response(fields: &str) -> String {
+    let hash = format!("0x{}", "11".repeat(32));
+    format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"blockHash":"{hash}","blockNumber":"0x12a05f2","from":"0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5","gas":"0x5208","gasPrice":"0x3b9aca00","hash":"{hash}","input":"0x","nonce":"0x7","to":"0x388c818ca8b9251b393131c08a736a67ccb19297","transactionIndex":"0x0","value":"0xde0b6b3a7640000","v":"0x25","r":"0x1","s":"0x2"{fields}}}}}"#
+    )
+}
+
+fn parse_transaction(fields: &str) -> Transaction {
+    serde_json::from_str::<Response<Transaction>>(&transaction_response(fields))
+        .expect("could not parse transaction")
+        .result
+        .expect("expected transaction")
+}
+
+#[test]
+fn transaction_legacy() {
+    let transaction = parse_transaction("");
+    assert_eq!(TransactionType::Legacy, transaction.transaction_type);
+    assert_eq!(U256::from(1_000_000_000), transaction.gas_price);
+    assert_eq!(None, transaction.max_fee_per_gas);
+    assert_eq!(None, transaction.access_list);
+    assert_eq!(None, transaction.chain_id);
+    assert_eq!(U256::exp10(18), transaction.value);
+    assert_eq!(U64::from(0x25), transaction.v);
+    assert!(transaction.input.is_empty());
+
+    let transaction = parse_transaction(r#","type":"0x0","chainId":"0x1""#);
+    assert_eq!(TransactionType::Legacy, transaction.transaction_type);
+    assert_eq!(Some(1), transaction.chain_id);
+}
+
+#[test]
+fn transaction_access_list() {
+    let key = format!("0x{}", "00".repeat(31) + "07");
+    let transaction = parse_transaction(&format!(
+        r#","type":"0x1","chainId":"0xa4b1","yParity":"0x1","accessList":[{{"address":"0x388c818ca8b9251b393131c08a736a67ccb19297","storageKeys":["{key}"]}}]"#
+    ));
+    assert_eq!(TransactionType::AccessList, transaction.transaction_type);
+    assert_eq!(Some(42161), transaction.chain_id);
+    assert_eq!(Some(U64::one()), transaction.y_parity);
+    let access_list = transaction.access_list.expect("expected access list");
+    assert_eq!(transaction.to, Some(access_list[0].address));
+    assert_eq!(vec![H256::from_low_u64_be(7)], access_list[0].storage_keys);
+}
+
+#[test]
+fn transaction_dynamic_fee() {
+    let transaction = parse_transaction(
+        r#","type":"0x2","chainId":"0x1","maxFeePerGas":"0x77359400","maxPriorityFeePerGas":"0x3b9aca00","accessList":[]"#,
+    );
+    assert_eq!(TransactionType::DynamicFee, transaction.transaction_type);
+    assert_eq!(Some(U256::from(2_000_000_000)), transaction.max_fee_per_gas);
+    assert_eq!(Some(U256::from(1_000_000_000)), transaction.max_priority_fee_per_gas);
+    assert_eq!(Some(vec![]), transaction.access_list);
+    assert_eq!(None, transaction.blob_versioned_hashes);
+}
+
+#[test]
+fn transaction_blob() {
+    let blob = format!("0x01{}", "ab".repeat(31));
+    let transaction = parse_transaction(&format!(
+        r#","type":"0x3","chainId":"0x1","maxFeePerGas":"0x77359400","maxPriorityFeePerGas":"0x3b9aca00","maxFeePerBlobGas":"0x1","accessList":[],"blobVersionedHashes":["{blob}"]"#
+    ));
+    assert_eq!(TransactionType::Blob, transaction.transaction_type);
+    assert_eq!(Some(U256::one()), transaction.max_fee_per_blob_gas);
+    assert_eq!(
+        Some(vec![H256::from_str(&blob).expect("could not parse hash")]),
+        transaction.blob_versioned_hashes
+    );
+}
+
+#[test]
+fn transaction_other() {
+    let transaction = parse_transaction(r#","type":"0x7e""#);
+    assert_eq!(TransactionType::Other(0x7e), transaction.transaction_type);
+    assert_eq!(0x7e, u8::from(transaction.transaction_type));
+}
+
+/// Returns a receipt response, with the given fields in addition to those of every receipt.
+fn receipt_response(fields: &str) -> String {
+    let hash = format!("0x{}", "11".repeat(32));
+    let mut bloom = Bloom::zero();
+    bloom.accrue(BloomInput::Raw(Address::repeat_byte(0x42).as_bytes()));
+    format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"blockHash":"{hash}","blockNumber":"0x12a05f2","contractAddress":null,"cumulativeGasUsed":"0x5208","effectiveGasPrice":"0x3b9aca00","from":"0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5","gasUsed":"0x5208","logs":[{{"address":"0x4242424242424242424242424242424242424242","topics":["{hash}"],"data":"0x2a","blockNumber":"0x12a05f2","transactionHash":"{hash}","transactionIndex":"0x0","blockHash":"{hash}","logIndex":"0x0","removed":false}}],"logsBloom":"{bloom:?}","to":"0x388c818ca8b9251b393131c08a736a67ccb19297","transactionHash":"{hash}","transactionIndex":"0x0"{fields}}}}}"#
+    )
+}
+
+fn parse_receipt(fields: &str) -> TransactionReceipt {
+    serde_json::from_str::<Response<TransactionReceipt>>(&receipt_response(fields))
+        .expect("could not parse receipt")
+        .result
+        .expect("expected receipt")
+}
+
+#[test]
+fn receipt_status() {
+    assert_eq!(ReceiptStatus::Success, parse_receipt(r#","status":"0x1","type":"0x2""#).status);
+    assert_eq!(ReceiptStatus::Failed, parse_receipt(r#","status":"0x0""#).status);
+    let root = H256::repeat_byte(0x22);
+    assert_eq!(
+        ReceiptStatus::PreByzantium(root),
+        parse_receipt(&format!(r#","root":"{root:?}""#)).status
+    );
+    for fields in ["", r#","status":"0x2""#] {
+        let response = serde_json::from_str::<Response<TransactionReceipt>>(&receipt_response(fields));
+        assert!(response.is_err());
+    }
+}
+
+#[test]
+fn receipt_logs() {
+    let receipt = parse_receipt(r#","status":"0x1""#);
+    assert_eq!(vec![H256::repeat_byte(0x11)], receipt.logs[0].topics);
+    assert_eq!(vec![0x2a], receipt.logs[0].data);
+    assert!(receipt.logs_bloom.contains_address(&receipt.logs[0].address));
+    assert!(!receipt.logs_bloom.contains_address(&receipt.from));
+    assert!(!receipt.logs_bloom.contains_topic(&receipt.logs[0].topics[0]));
+}
+
+#[test]
+fn receipt_fee() {
+    let receipt = parse_receipt(r#","status":"0x1""#);
+    assert_eq!(None, receipt.blob_gas_used);
+    assert_eq!(U256::from(21_000_000_000_000u64), receipt.fee());
+
+    let receipt = parse_receipt(r#","status":"0x1","type":"0x3","blobGasUsed":"0x20000","blobGasPrice":"0x2""#);
+    assert_eq!(TransactionType::Blob, receipt.transaction_type);
+    assert_eq!(Some(131_072), receipt.blob_gas_used);
+    assert_eq!(Some(U256::from(2)), receipt.blob_gas_price);
+    assert_eq!(U256::from(21_000_000_262_144u64), receipt.fee());
+}
+
+proptest! {
+    #[test]
+    fn response_never_panics(body in response()) {
+        let _ = serde_json::from_str::<Response<serde_json::Value>>(&body);
+        let _ = serde_json::from_str::<Response<Option<Block>>>(&body);
+        let _ = serde_json::from_str::<Response<Option<Transaction>>>(&body);
+        let _ = serde_json::from_str::<Response<Option<TransactionReceipt>>>(&body);
+    }
+}
+
+#[tokio::test]
+async fn block_id() -> Result<(), APIError> {
+    let transport = FakeTransport::new(r#"{"jsonrpc":"2.0","id":1,"result":"0x"}"#);
+    let client = transport.client();
+    let address = Address::zero();
+    client.call(&address, "0x", None).await?;
+    client.code(&address, Some(BlockNumber::from(0x10).into())).await?;
+
+    // A storage value is a 32-byte word
+    let storage = FakeTransport::new(format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{:?}"}}"#, slots::word(42)));
+    let value = storage
+        .client()
+        .storage_value(&address, slots::word(0), Some(BlockId::Safe))
+        .await?;
+    assert_eq!(slots::word(42), value);
+
+    let tags = |transport: &FakeTransport| {
+        transport
+            .requests
+            .lock()
+            .expect("lock poisoned")
+            .iter()
+            .map(|request| {
+                request
+                    .parameters
+                    .iter()
+                    .find(|(key, _)| key == "tag")
+                    .expect("expected tag")
+                    .1
+                    .clone()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(vec!["latest".to_string(), "0x10".to_string()], tags(&transport));
+    assert_eq!(vec!["safe".to_string()], tags(&storage));
+    Ok(())
+}
+
+#[tokio::test]
+async fn block_hash_unsupported() {
+    let transport = FakeTransport::new(r#"{"jsonrpc":"2.0","id":1,"result":"0x"}"#);
+    let client = transport.client();
+    let address = Address::zero();
+    let block = BlockId::Hash(H256::repeat_byte(0x11));
+    let unsupported = |result: Result<(), APIError>| matches!(result, Err(APIError::UnsupportedBlock { block: b }) if b == block);
+    assert!(unsupported(client.call(&address, "0x", Some(block)).await.map(|_| ())));
+    assert!(unsupported(client.code(&address, Some(block)).await.map(|_| ())));
+    assert!(unsupported(
+        client.storage_value(&address, slots::word(0), Some(block)).await.map(|_| ())
+    ));
+    assert!(unsupported(client.transactions(&address, Some(block)).await.map(|_| ())));
+    // Rejected before being sent
+    assert!(transport.requests.lock().expect("lock poisoned").is_empty());
+}
+
+/// Returns a client whose calls revert with the given revert data.
+fn reverted(data: &[u8]) -> Client {
+    FakeTransport::new(format!(
+        r#"{{"jsonrpc":"2.0","id":1,"error":{{"code":3,"message":"execution reverted","data":"0x{}"}}}}"#,
+        hex::encode(data)
+    ))
+    .client()
+}
+
+/// Returns revert data for an error, from its name and parameters.
+fn revert_data(name: &str, types: &[ParamType], parameters: &[Token]) -> Vec<u8> {
+    [&ethabi::short_signature(name, types)[..], &ethabi::encode(parameters)].concat()
+}
+
+#[tokio::test]
+async fn call_reverted_with_reason() {
+    let data = revert_data("Error", &[ParamType::String], &[Token::String("insufficient balance".to_string())]);
+    let address = Address::zero();
+    let error = reverted(&data).call(&address, "0x", None).await.expect_err("expected revert");
+    assert!(matches!(error, APIError::RPCError { code: 3, .. }));
+    assert_eq!(Some(data), error.revert_data());
+    assert_eq!(Some(Revert::Error("insufficient balance".to_string())), error.revert());
+}
+
+#[tokio::test]
+async fn estimate_gas_panicked() {
+    let data = revert_data("Panic", &[ParamType::Uint(256)], &[Token::Uint(U256::from(0x11))]);
+    let address = Address::zero();
+    let error = reverted(&data)
+        .estimate_gas(&address, "0x", U256::zero(), 21000, U256::one())
+        .await
+        .expect_err("expected revert");
+    assert_eq!(Some(Revert::Panic(U256::from(0x11))), error.revert());
+}
+
+#[tokio::test]
+async fn call_reverted_with_custom_error() {
+    const ABI: &str = r#"[{"type":"error","name":"InsufficientBalance","inputs":[{"name":"available","type":"uint256"},{"name":"required","type":"uint256"}]}]"#;
+    let abi = ABI::load(ABI.as_bytes()).expect("could not load ABI");
+    let parameters = vec![Token::Uint(U256::from(1)), Token::Uint(U256::from(2))];
+    let data = revert_data("InsufficientBalance", &[ParamType::Uint(256), ParamType::Uint(256)], &parameters);
+    let address = Address::zero();
+    let error = reverted(&data).call(&address, "0x", None).await.expect_err("expected revert");
+
+    // Without the ABI, the revert data cannot be decoded
+    assert_eq!(Some(Revert::Raw(data)), error.revert());
+    assert_eq!(
+        Some(Revert::Custom {
+            name: "InsufficientBalance".to_string(),
+            parameters
+        }),
+        error.revert_with(&abi)
+    );
+}
+
+#[test]
+fn revert_data_nested() {
+    let error = serde_json::from_str::<Response<String>>(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"execution reverted","data":{"data":"0x1234"}}}"#,
+    )
+    .expect("could not parse response");
+    match error.result {
+        Err(super::Failure::Rpc { data: Some(data), .. }) => assert_eq!(Some(vec![0x12, 0x34]), super::revert_data(&data)),
+        result => panic!("expected RPC error, got {:?}", result),
+    }
+}
+
+#[test]
+fn revert_display() {
+    assert_eq!("reverted: no", Revert::Error("no".to_string()).to_string());
+    assert_eq!("panicked with code 0x11", Revert::Panic(U256::from(0x11)).to_string());
+    assert_eq!("reverted with 0x1234", Revert::Raw(vec![0x12, 0x34]).to_string());
+}
+
+/// Returns the result of a JSON-RPC response, as JSON.
+fn result(response: &str) -> String {
+    let response: serde_json::Value = serde_json::from_str(response).expect("invalid JSON");
+    response["result"].to_string()
+}
+
+#[test]
+fn round_trips() {
+    let root = format!("0x{}", "22".repeat(32));
+    round_trip::<Block>(&result(&block_response(r#","totalDifficulty":"0x3""#)));
+    round_trip::<Block>(&result(&block_response(&format!(
+        r#","baseFeePerGas":"0x3b9aca00","withdrawals":[{{"index":"0x2a","validatorIndex":"0x10","address":"0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5","amount":"0x11e1a300"}}],"withdrawalsRoot":"{root}","blobGasUsed":"0x20000","excessBlobGas":"0x0","parentBeaconBlockRoot":"{root}""#
+    ))));
+
+    round_trip::<Transaction>(&result(&transaction_response(r#","type":"0x0""#)));
+    let transaction = round_trip::<Transaction>(&result(&transaction_response(&format!(
+        r#","type":"0x3","maxFeePerGas":"0x77359400","maxPriorityFeePerGas":"0x3b9aca00","maxFeePerBlobGas":"0x1","accessList":[{{"address":"0x388c818ca8b9251b393131c08a736a67ccb19297","storageKeys":["{root}"]}}],"blobVersionedHashes":["{root}"],"chainId":"0x1","yParity":"0x1""#
+    ))));
+    assert_eq!(TransactionType::Blob, transaction.transaction_type);
+
+    // A pending transaction is not yet within a block
+    let mut pending: serde_json::Value = serde_json::from_str(&result(&transaction_response(r#","type":"0x2""#))).expect("invalid JSON");
+    for field in ["blockHash", "blockNumber", "transactionIndex"] {
+        pending[field] = serde_json::Value::Null;
+    }
+    let transaction = round_trip::<Transaction>(&pending.to_string());
+    assert_eq!(
+        (None, None, None),
+        (transaction.block_hash, transaction.block_number, transaction.transaction_index)
+    );
+
+    round_trip::<TransactionReceipt>(&result(&receipt_response(r#","status":"0x1","type":"0x2""#)));
+    let receipt = round_trip::<TransactionReceipt>(&result(&receipt_response(&format!(r#","root":"{root}","type":"0x0""#))));
+    assert_eq!(ReceiptStatus::PreByzantium(H256::repeat_byte(0x22)), receipt.status);
+    round_trip::<TransactionReceipt>(&result(&receipt_response(
+        r#","status":"0x0","type":"0x3","blobGasUsed":"0x20000","blobGasPrice":"0x1""#,
+    )));
+
+    // Indexes beyond 16 bits, as in blocks with many transactions or logs
+    let mut receipt: serde_json::Value =
+        serde_json::from_str(&result(&receipt_response(r#","status":"0x1","type":"0x2""#))).expect("invalid JSON");
+    receipt["transactionIndex"] = "0x10000".into();
+    receipt["logs"][0]["transactionIndex"] = "0x10000".into();
+    receipt["logs"][0]["logIndex"] = "0x186a0".into();
+    let receipt = round_trip::<TransactionReceipt>(&receipt.to_string());
+    assert_eq!(65_536, receipt.transaction_index);
+    assert_eq!((65_536, 100_000), (receipt.logs[0].transaction_index, receipt.logs[0].log_index));
+}
+
+#[tokio::test]
+async fn storage_value_slot() -> Result<(), crate::APIError> {
+    let transport =
+        FakeTransport::new(r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000de0b6b3a7640000"}"#);
+    let client = transport.client();
+    let address = Address::repeat_byte(0x42);
+    let slot = slots::mapping(slots::word(0), H256::from(address));
+    let value = client.storage_value(&address, slot, None).await?;
+    assert_eq!(slots::word(1_000_000_000_000_000_000u64), value);
+    let requests = transport.requests.lock().expect("lock poisoned");
+    assert!(requests[0].parameters.contains(&("position".to_string(), format!("{:#x}", slot))));
+    Ok(())
+}
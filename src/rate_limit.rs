@@ -0,0 +1,132 @@
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Instant};
+
+/// A client-side rate limit, matching the request rate of an Etherscan API plan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RateLimit {
+    /// The free plan, at 5 requests per second
+    Free,
+    /// The standard plan, at 10 requests per second
+    Standard,
+    /// The advanced plan, at 20 requests per second
+    Advanced,
+    /// The professional plan, at 30 requests per second
+    Professional,
+    /// A custom number of requests per period
+    Custom { requests: u32, period: Duration },
+}
+
+impl RateLimit {
+    /// Returns the number of requests allowed per period.
+    pub fn requests(&self) -> u32 {
+        match self {
+            RateLimit::Free => 5,
+            RateLimit::Standard => 10,
+            RateLimit::Advanced => 20,
+            RateLimit::Professional => 30,
+            RateLimit::Custom { requests, .. } => *requests,
+        }
+    }
+
+    /// Returns the period over which the number of requests is allowed.
+    pub fn period(&self) -> Duration {
+        match self {
+            RateLimit::Custom { period, .. } => *period,
+            _ => Duration::from_secs(1),
+        }
+    }
+}
+
+/// A token bucket, which callers wait on for a slot before sending a request.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    // Tokens added per second
+    rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    // Available tokens, which is negative when slots have been reserved ahead of time
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: &RateLimit) -> RateLimiter {
+        let capacity = limit.requests().max(1) as f64;
+        RateLimiter {
+            capacity,
+            rate: capacity / limit.period().as_secs_f64(),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits until a request slot is available.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().await;
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+            bucket.updated = now;
+
+            // Reserve a slot, waiting for the bucket to refill if none are available
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        };
+        sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RateLimit, RateLimiter};
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[test]
+    fn plans() {
+        assert_eq!(5, RateLimit::Free.requests());
+        assert_eq!(Duration::from_secs(1), RateLimit::Free.period());
+        let custom = RateLimit::Custom {
+            requests: 100,
+            period: Duration::from_secs(60),
+        };
+        assert_eq!(100, custom.requests());
+        assert_eq!(Duration::from_secs(60), custom.period());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_within_limit() {
+        let limiter = RateLimiter::new(&RateLimit::Free);
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+        assert_eq!(Duration::ZERO, start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_waits_for_slot() {
+        let limiter = RateLimiter::new(&RateLimit::Custom {
+            requests: 2,
+            period: Duration::from_secs(1),
+        });
+        let start = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire().await;
+        }
+        // Two requests immediately, then one every half second
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert!(start.elapsed() < Duration::from_millis(1100));
+    }
+}
@@ -6,17 +6,42 @@ use serde::{
 use std::fmt;
 use std::marker::PhantomData;
 
+/// A response from the explorer API, holding either its result or the failure returned in its place.
 #[derive(Debug)]
 pub struct Response<T> {
-    pub result: T,
+    pub result: std::result::Result<T, Failure>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug)]
 pub enum ResponseStatus {
     Success = 1,
     Failed = 0,
 }
 
+impl<'de> Deserialize<'de> for ResponseStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        match value.as_str() {
+            "1" => Ok(ResponseStatus::Success),
+            "0" => Ok(ResponseStatus::Failed),
+            _ => Err(de::Error::invalid_value(de::Unexpected::Str(&value), &"\"0\" or \"1\"")),
+        }
+    }
+}
+
+/// A failure returned by the explorer API in place of a result.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Failure {
+    /// A response with a status of '0', along with its message and result (which usually describes the failure)
+    Api { message: String, result: serde_json::Value },
+    /// A JSON-RPC error, returned by the proxy module, along with any data (such as the revert data of a call)
+    Rpc {
+        code: i64,
+        message: String,
+        data: Option<serde_json::Value>,
+    },
+}
+
 impl<'de, T: Deserialize<'de>> Deserialize<'de> for Response<T> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
         struct ResultVisitor<T>(PhantomData<fn() -> T>);
@@ -35,69 +60,49 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Response<T> {
             fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> std::result::Result<Response<T>, V::Error> {
                 let mut status = None;
                 let mut message: Option<String> = None;
-                let mut result = None;
-                while let Some(key) = map.next_key()? {
-                    match key {
+                let mut result: Option<serde_json::Value> = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
                         STATUS => {
                             if status.is_some() {
                                 return Err(de::Error::duplicate_field(STATUS));
                             }
-                            let value: String = map.next_value()?;
-                            status = Some(match value.as_str() {
-                                "1" => ResponseStatus::Success,
-                                "0" => ResponseStatus::Failed,
-                                _ => panic!("Unknown value: {}", value),
-                            });
+                            status = Some(map.next_value::<ResponseStatus>()?);
                         }
                         MESSAGE => {
                             if message.is_some() {
                                 return Err(de::Error::duplicate_field(MESSAGE));
                             }
                             message = Some(map.next_value()?);
-
-                            if let Some(ResponseStatus::Failed) = status {
-                                if let Some(message) = { &message } {
-                                    if message.starts_with("NOTOK") {
-                                        // Get message from next value
-                                        let next: Option<(String, String)> = map.next_entry()?;
-                                        if let Some((_, value)) = next {
-                                            return Err(de::Error::custom(value));
-                                        }
-                                    }
-                                }
-                            }
                         }
                         RESULT => {
                             if result.is_some() {
                                 return Err(de::Error::duplicate_field(RESULT));
                             }
-                            if status.is_none() {
-                                return Err(de::Error::custom("status not deserialised yet"));
-                            }
-
-                            // Return result message as error if failed
-                            if let Some(ResponseStatus::Failed) = status {
-                                if let Some(message) = { &message } {
-                                    // Exclude empty result, which returns as status failed
-                                    if message != "No transactions found" {
-                                        let value: String = map.next_value()?;
-                                        return Err(de::Error::custom(value));
-                                    }
-                                }
-                            }
-
                             result = Some(map.next_value()?);
                         }
                         _ => {
                             // Ignore value
-                            let _ = map.next_value::<()>();
+                            map.next_value::<de::IgnoredAny>()?;
                         }
                     }
                 }
-                status.ok_or_else(|| de::Error::missing_field(STATUS))?;
-                message.ok_or_else(|| de::Error::missing_field(MESSAGE))?;
+                let status = status.ok_or_else(|| de::Error::missing_field(STATUS))?;
+                let message = message.ok_or_else(|| de::Error::missing_field(MESSAGE))?;
                 let result = result.ok_or_else(|| de::Error::missing_field(RESULT))?;
-                Ok(Response::<T> { result })
+
+                // Return the failure in place of the result, excluding empty results which return as status failed
+                if let ResponseStatus::Failed = status {
+                    if let Some(empty) = empty_result::<T>(&message) {
+                        return Ok(Response { result: Ok(empty) });
+                    }
+                    return Ok(Response {
+                        result: Err(Failure::Api { message, result }),
+                    });
+                }
+                T::deserialize(result)
+                    .map(|result| Response { result: Ok(result) })
+                    .map_err(de::Error::custom)
             }
         }
 
@@ -105,3 +110,105 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Response<T> {
         deserializer.deserialize_struct("Response", FIELDS, ResultVisitor(PhantomData))
     }
 }
+
+/// Returns an empty result for the message of a response which found nothing, such as 'No transactions found' or
+/// 'No records found', which is returned with a failed status. Returns none for other messages, or if the result is
+/// not a list.
+pub(crate) fn empty_result<'de, T: Deserialize<'de>>(message: &str) -> Option<T> {
+    if !is_nothing_found(message) {
+        return None;
+    }
+    T::deserialize(serde_json::Value::Array(Vec::new())).ok()
+}
+
+/// The messages of responses which found nothing, which are returned with a failed status.
+const NOTHING_FOUND: &[&str] = &[
+    "No transactions found",
+    "No records found",
+    "No token transfers found",
+    "No internal transactions found",
+    "No data found",
+];
+
+/// Returns true for the message of a response which found nothing, such as 'No transactions found'.
+pub(crate) fn is_nothing_found(message: &str) -> bool {
+    let message = message.trim();
+    NOTHING_FOUND
+        .iter()
+        .any(|nothing_found| nothing_found.eq_ignore_ascii_case(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{empty_result, Failure, Response};
+    use crate::accounts::Balance;
+    use crate::stats::ChainSize;
+    use crate::tests::{json, response};
+    use proptest::prelude::*;
+
+    #[test]
+    fn unknown_status() {
+        let response = serde_json::from_str::<Response<String>>(r#"{"status":"2","message":"OK","result":"1"}"#);
+        assert!(response.is_err());
+        let response = serde_json::from_str::<Response<String>>(r#"{"status":1,"message":"OK","result":"1"}"#);
+        assert!(response.is_err());
+    }
+
+    #[test]
+    fn failure() {
+        let response =
+            serde_json::from_str::<Response<u64>>(r#"{"status":"0","message":"NOTOK","result":"Error! Missing Or invalid Module name"}"#)
+                .expect("could not parse response");
+        assert_eq!(
+            Err(Failure::Api {
+                message: "NOTOK".to_string(),
+                result: "Error! Missing Or invalid Module name".into()
+            }),
+            response.result
+        );
+    }
+
+    #[test]
+    fn empty() {
+        for message in [
+            "No transactions found",
+            "No records found",
+            "No token transfers found",
+            "No internal transactions found",
+            "No data found",
+            "no transactions found",
+        ] {
+            let body = format!(r#"{{"status":"0","message":"{}","result":[]}}"#, message);
+            let response = serde_json::from_str::<Response<Vec<Balance>>>(&body).expect("could not parse response");
+            assert!(response.result.expect("expected empty result").is_empty());
+        }
+        // Other messages which read as nothing found remain failures
+        for message in ["No matching contract found", "No API key found"] {
+            let body = format!(r#"{{"status":"0","message":"{}","result":[]}}"#, message);
+            let response = serde_json::from_str::<Response<Vec<Balance>>>(&body).expect("could not parse response");
+            assert!(response.result.is_err());
+        }
+        assert!(empty_result::<Vec<Balance>>("NOTOK").is_none());
+        assert!(empty_result::<String>("No records found").is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics_on_arbitrary_json(body in any::<String>()) {
+            let _ = serde_json::from_str::<Response<serde_json::Value>>(&body);
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_responses(body in response()) {
+            let _ = serde_json::from_str::<Response<serde_json::Value>>(&body);
+            let _ = serde_json::from_str::<Response<String>>(&body);
+            let _ = serde_json::from_str::<Response<Vec<Balance>>>(&body);
+            let _ = serde_json::from_str::<Response<Vec<ChainSize>>>(&body);
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_values(value in json()) {
+            let _ = serde_json::from_value::<Response<serde_json::Value>>(value);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::APIError;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A policy for retrying failed requests, with exponential backoff and jitter between attempts.
+///
+/// By default, errors which are [retryable](APIError::is_retryable) are retried, such as rate limit errors, timeouts,
+/// connection errors and server (5xx) errors. A delay suggested by the API is waited for when longer than the backoff,
+/// up to the maximum backoff.
+///
+/// # Example
+///
+/// ```
+/// # use etherscan::{retry::RetryPolicy, APIError};
+/// # use std::time::Duration;
+/// let policy = RetryPolicy::new(5)
+///     .backoff(Duration::from_millis(250), Duration::from_secs(10))
+///     .retry_if(|e| matches!(e, APIError::RateLimitReached { .. }));
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable: Arc<dyn Fn(&APIError) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy.
+    ///
+    /// # Arguments
+    ///
+    /// * 'max_attempts' - The maximum number of attempts per request, including the first
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retryable: Arc::new(APIError::is_retryable),
+        }
+    }
+
+    /// Sets the backoff before the first retry, which doubles for each subsequent retry up to a maximum.
+    ///
+    /// # Arguments
+    ///
+    /// * 'initial' - The backoff before the first retry
+    /// * 'max' - The maximum backoff between retries
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> RetryPolicy {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets whether a random jitter is applied to each backoff, so concurrent callers do not retry in lockstep.
+    ///
+    /// # Arguments
+    ///
+    /// * 'jitter' - Whether jitter is applied, which defaults to true
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicy {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets which errors are retried.
+    ///
+    /// # Arguments
+    ///
+    /// * 'retryable' - A predicate returning true for errors that should be retried
+    pub fn retry_if(mut self, retryable: impl Fn(&APIError) -> bool + Send + Sync + 'static) -> RetryPolicy {
+        self.retryable = Arc::new(retryable);
+        self
+    }
+
+    /// Returns the maximum number of attempts per request, including the first.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the backoff before a retry, or none if the error should not be retried.
+    ///
+    /// # Arguments
+    ///
+    /// * 'attempt' - The number of attempts made so far
+    /// * 'error' - The error returned by the last attempt
+    pub(crate) fn backoff_for(&self, attempt: u32, error: &APIError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !(self.retryable)(error) {
+            return None;
+        }
+        let exponent = attempt.saturating_sub(1).min(31);
+        let mut backoff = self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff);
+        if self.jitter {
+            // Equal jitter: half of the backoff is fixed, the other half random
+            let half = backoff / 2;
+            backoff = half + half.mul_f64(fastrand::f64());
+        }
+        Some(
+            error
+                .retry_delay()
+                .map_or(backoff, |delay| delay.max(backoff).min(self.max_backoff)),
+        )
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use crate::APIError;
+    use std::time::Duration;
+
+    fn rate_limit() -> APIError {
+        APIError::RateLimitReached {
+            message: "Max rate limit reached".to_string(),
+            context: Default::default(),
+        }
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::new(4)
+            .backoff(Duration::from_millis(100), Duration::from_millis(300))
+            .jitter(false);
+        assert_eq!(Some(Duration::from_millis(100)), policy.backoff_for(1, &rate_limit()));
+        assert_eq!(Some(Duration::from_millis(200)), policy.backoff_for(2, &rate_limit()));
+        assert_eq!(Some(Duration::from_millis(300)), policy.backoff_for(3, &rate_limit()));
+        assert_eq!(None, policy.backoff_for(4, &rate_limit()));
+    }
+
+    #[test]
+    fn backoff_jitter() {
+        let policy = RetryPolicy::new(2).backoff(Duration::from_millis(100), Duration::from_millis(100));
+        for _ in 0..100 {
+            let backoff = policy.backoff_for(1, &rate_limit()).expect("expected backoff");
+            assert!(backoff >= Duration::from_millis(50) && backoff <= Duration::from_millis(100));
+        }
+    }
+
+    fn invalid_address() -> APIError {
+        APIError::InvalidAddress {
+            context: Default::default(),
+        }
+    }
+
+    #[test]
+    fn not_retryable() {
+        let policy = RetryPolicy::new(3);
+        assert_eq!(None, policy.backoff_for(1, &invalid_address()));
+        assert_eq!(
+            None,
+            policy.backoff_for(
+                1,
+                &APIError::ContractNotVerified {
+                    context: Default::default()
+                }
+            )
+        );
+        let server_error = APIError::ServerError {
+            status: 503,
+            context: Default::default(),
+        };
+        assert!(policy.backoff_for(1, &server_error).is_some());
+    }
+
+    #[test]
+    fn retry_delay() {
+        let policy = RetryPolicy::new(3)
+            .backoff(Duration::from_millis(100), Duration::from_secs(10))
+            .jitter(false);
+        let error = APIError::RateLimitReached {
+            message: "Max calls per sec rate limit reached (5/sec)".to_string(),
+            context: Default::default(),
+        };
+        assert_eq!(Some(Duration::from_secs(1)), policy.backoff_for(1, &error));
+        let error = APIError::RateLimitReached {
+            message: "Too Many Requests".to_string(),
+            context: Box::new(crate::RequestContext {
+                retry_after: Some(Duration::from_secs(5)),
+                ..Default::default()
+            }),
+        };
+        assert_eq!(Some(Duration::from_secs(5)), policy.backoff_for(1, &error));
+    }
+
+    #[test]
+    fn retry_delay_max_backoff() {
+        let policy = RetryPolicy::new(3)
+            .backoff(Duration::from_millis(100), Duration::from_secs(2))
+            .jitter(false);
+        let error = APIError::RateLimitReached {
+            message: "Too Many Requests".to_string(),
+            context: Box::new(crate::RequestContext {
+                retry_after: Some(Duration::from_secs(3600)),
+                ..Default::default()
+            }),
+        };
+        assert_eq!(Some(Duration::from_secs(2)), policy.backoff_for(1, &error));
+    }
+
+    #[test]
+    fn retry_if() {
+        let policy = RetryPolicy::new(3).retry_if(|e| matches!(e, APIError::InvalidAddress { .. }));
+        assert!(policy.backoff_for(1, &invalid_address()).is_some());
+        assert_eq!(None, policy.backoff_for(1, &rate_limit()));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::U256;
+use ethabi::ethereum_types::H256;
+use sha3::{Digest, Keccak256};
+
+/// Returns the Keccak-256 hash of the given bytes.
+///
+/// # Arguments
+///
+/// * 'bytes' - The bytes to hash
+pub fn keccak256(bytes: impl AsRef<[u8]>) -> H256 {
+    H256::from_slice(&Keccak256::digest(bytes.as_ref()))
+}
+
+/// Returns a value as a 32-byte storage word, such as the position of a state variable or an integer mapping key.
+///
+/// Addresses and other hashes can be used as mapping keys directly, as `H256::from(address)` pads them the same way.
+///
+/// # Arguments
+///
+/// * 'value' - The value, such as 0 for the first state variable of a contract
+pub fn word(value: impl Into<U256>) -> H256 {
+    let mut bytes = [0u8; 32];
+    value.into().to_big_endian(&mut bytes);
+    H256(bytes)
+}
+
+/// Returns the slot of a mapping entry, which is `keccak(key . slot)`.
+///
+/// # Arguments
+///
+/// * 'slot' - The slot of the mapping
+/// * 'key' - The key of the entry, padded to 32 bytes
+pub fn mapping(slot: H256, key: H256) -> H256 {
+    keccak256([key.as_bytes(), slot.as_bytes()].concat())
+}
+
+/// Returns the slot of a mapping entry whose key is a `string` or `bytes`, which is hashed without padding.
+///
+/// # Arguments
+///
+/// * 'slot' - The slot of the mapping
+/// * 'key' - The key of the entry
+pub fn mapping_with_bytes(slot: H256, key: impl AsRef<[u8]>) -> H256 {
+    keccak256([key.as_ref(), slot.as_bytes()].concat())
+}
+
+/// Returns the slot of an entry of nested mappings, such as `allowance[owner][spender]`.
+///
+/// # Arguments
+///
+/// * 'slot' - The slot of the outermost mapping
+/// * 'keys' - The keys of the entry, from the outermost mapping inwards
+pub fn nested_mapping(slot: H256, keys: &[H256]) -> H256 {
+    keys.iter().fold(slot, |slot, key| mapping(slot, *key))
+}
+
+/// Returns the slot of an element of a dynamic array, whose elements start at `keccak(slot)`. The length of the array
+/// is stored at the slot itself.
+///
+/// # Arguments
+///
+/// * 'slot' - The slot of the array
+/// * 'index' - The index of the element
+/// * 'element_slots' - The number of slots each element occupies, such as 1 for value types or more for structs
+pub fn array_element(slot: H256, index: impl Into<U256>, element_slots: impl Into<U256>) -> H256 {
+    offset(keccak256(slot), index.into().overflowing_mul(element_slots.into()).0)
+}
+
+/// Returns the slot at an offset from another, such as that of a struct member from the slot of the struct.
+///
+/// # Arguments
+///
+/// * 'slot' - The slot, such as that of a struct
+/// * 'offset' - The number of slots from the slot, such as that of a struct member
+pub fn offset(slot: H256, offset: impl Into<U256>) -> H256 {
+    word(U256::from_big_endian(slot.as_bytes()).overflowing_add(offset.into()).0)
+}
+
+/// Returns a value packed into a storage word alongside others, right-aligned, or none if it does not fit within the
+/// word.
+///
+/// Solidity packs values smaller than 32 bytes into the same slot, starting from its lowest-order bytes.
+///
+/// # Arguments
+///
+/// * 'value' - The storage word, such as returned by [`storage_value`](crate::proxy::Proxy::storage_value)
+/// * 'offset' - The offset of the packed value in bytes, from the lowest-order byte of the word
+/// * 'size' - The size of the packed value in bytes, such as 20 for an address
+pub fn packed(value: H256, offset: usize, size: usize) -> Option<H256> {
+    let end = 32usize.checked_sub(offset)?;
+    let start = end.checked_sub(size)?;
+    let mut bytes = [0u8; 32];
+    bytes[32 - size..].copy_from_slice(&value.as_bytes()[start..end]);
+    Some(H256(bytes))
+}
+
+/// Returns an EIP-1967 proxy slot, which is `keccak(label) - 1`, such as the implementation slot for the label
+/// 'eip1967.proxy.implementation'.
+///
+/// # Arguments
+///
+/// * 'label' - The label of the slot, such as 'eip1967.proxy.implementation', 'eip1967.proxy.admin' or
+///   'eip1967.proxy.beacon'
+pub fn eip1967(label: &str) -> H256 {
+    word(U256::from_big_endian(keccak256(label).as_bytes()).overflowing_sub(U256::one()).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+    use std::str::FromStr;
+
+    fn hash(value: &str) -> H256 {
+        H256::from_str(value).expect("could not parse hash")
+    }
+
+    #[test]
+    fn keccak() {
+        assert_eq!(
+            hash("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+            keccak256([])
+        );
+    }
+
+    #[test]
+    fn mappings() {
+        let owner = Address::from_str("0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae").expect("could not parse address");
+        let spender = Address::repeat_byte(0x42);
+        let key = H256::from(owner);
+        assert_eq!(owner.as_bytes(), &key.as_bytes()[12..]);
+        assert_eq!(keccak256([key.as_bytes(), word(1).as_bytes()].concat()), mapping(word(1), key));
+        assert_eq!(
+            mapping(mapping(word(2), key), H256::from(spender)),
+            nested_mapping(word(2), &[key, H256::from(spender)])
+        );
+        assert_eq!(word(2), nested_mapping(word(2), &[]));
+        assert_eq!(
+            keccak256([&b"name"[..], word(3).as_bytes()].concat()),
+            mapping_with_bytes(word(3), "name")
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let start = hash("0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563");
+        assert_eq!(start, array_element(word(0), 0, 1));
+        assert_eq!(
+            hash("0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e567"),
+            array_element(word(0), 2, 2)
+        );
+        assert_eq!(word(0), offset(word(U256::MAX), 1));
+    }
+
+    #[test]
+    fn packing() {
+        let value = hash("0x0000000000000000000000014242424242424242424242424242424242424242");
+        assert_eq!(Some(H256::from(Address::repeat_byte(0x42))), packed(value, 0, 20));
+        assert_eq!(Some(word(1)), packed(value, 20, 1));
+        assert_eq!(Some(value), packed(value, 0, 32));
+        assert_eq!(None, packed(value, 20, 13));
+        assert_eq!(None, packed(value, 33, 0));
+    }
+
+    #[test]
+    fn eip1967_slots() {
+        assert_eq!(
+            hash("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"),
+            eip1967("eip1967.proxy.implementation")
+        );
+        assert_eq!(
+            hash("0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"),
+            eip1967("eip1967.proxy.admin")
+        );
+    }
+}
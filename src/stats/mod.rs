@@ -1,10 +1,13 @@
 use super::Result;
-use crate::{BlockNumber, Client, TypeExtensions, ACTION, MODULE};
+use crate::convert::TokenAmount;
+use crate::{Amount, BlockNumber, Client, DecimalU256, TypeExtensions, ACTION, MODULE, U256};
 use async_trait::async_trait;
-use chrono::{Date, DateTime, NaiveDate, Utc};
+#[allow(deprecated)]
+use chrono::Date;
+use chrono::{DateTime, NaiveDate, Utc};
 use ethabi::Address;
 use serde::de::Error;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{serde_as, DisplayFromStr, TimestampSecondsWithFrac};
 
 #[cfg(test)]
@@ -39,15 +42,28 @@ pub trait Stats {
     /// Returns the total number of discoverable Ethereum nodes.
     async fn nodes(&self) -> Result<NodeStats>;
 
-    /// Returns the current amount of an ERC-20 token in circulation.
+    /// Returns the current amount of an ERC-20 token in circulation, in the smallest unit of the token. See
+    /// [`token_supply_amount`](Stats::token_supply_amount) for the supply as a [`TokenAmount`].
     ///
     /// # Arguments
     ///
     /// * 'contract_address' - the contract address of the ERC-20 token
-    async fn token_supply(&self, contract_address: &Address) -> Result<u128>;
+    async fn token_supply(&self, contract_address: &Address) -> Result<U256>;
+
+    /// Returns the current amount of an ERC-20 token in circulation, with the decimals and symbol of the token.
+    ///
+    /// As with [`erc20_token_amount`](crate::accounts::Accounts::erc20_token_amount), the decimals and symbol are given
+    /// by the caller, as the supply endpoint returns only the raw amount.
+    ///
+    /// # Arguments
+    ///
+    /// * 'contract_address' - the contract address of the ERC-20 token
+    /// * 'decimals' - the number of decimals of the token
+    /// * 'symbol' - the symbol of the token
+    async fn token_supply_amount(&self, contract_address: &Address, decimals: u8, symbol: &str) -> Result<TokenAmount>;
 
     /// Returns the current amount of Ether in circulation excluding ETH2 Staking rewards and EIP1559 burnt fees
-    async fn total_supply(&self) -> Result<u128>;
+    async fn total_supply(&self) -> Result<U256>;
 
     /// Returns the current amount of Ether in circulation, ETH2 Staking rewards and EIP1559 burnt fees statistics.
     async fn total_supply_stats(&self) -> Result<TotalSupply>;
@@ -87,18 +103,23 @@ impl Stats for Client {
         self.get(parameters).await
     }
 
-    async fn token_supply(&self, contract_address: &Address) -> Result<u128> {
+    async fn token_supply(&self, contract_address: &Address) -> Result<U256> {
         let parameters = &[
             (MODULE, STATS),
             (ACTION, "tokensupply"),
             ("contractaddress", &TypeExtensions::format(contract_address)),
         ];
-        self.get::<String>(parameters).await.map(|v| v.parse::<u128>().unwrap_or(0))
+        self.get::<Amount>(parameters).await.map(|amount| amount.0)
+    }
+
+    async fn token_supply_amount(&self, contract_address: &Address, decimals: u8, symbol: &str) -> Result<TokenAmount> {
+        let supply = self.token_supply(contract_address).await?;
+        Ok(TokenAmount::new(supply, decimals, symbol))
     }
 
-    async fn total_supply(&self) -> Result<u128> {
+    async fn total_supply(&self) -> Result<U256> {
         let parameters = &[(MODULE, STATS), (ACTION, "ethsupply")];
-        self.get::<String>(parameters).await.map(|v| v.parse::<u128>().unwrap_or(0))
+        self.get::<Amount>(parameters).await.map(|amount| amount.0)
     }
 
     /// Returns the current amount of Ether in circulation, ETH2 Staking rewards and EIP1559 burnt fees statistics.
@@ -108,30 +129,33 @@ impl Stats for Client {
     }
 }
 
+#[allow(deprecated)]
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChainSize {
-    #[serde(deserialize_with = "crate::de_string_to_block_number")]
+    #[serde(
+        deserialize_with = "crate::de_string_to_block_number",
+        serialize_with = "crate::se_block_number_to_string"
+    )]
     pub block_number: BlockNumber,
     #[serde(rename = "chainTimeStamp")]
-    #[serde(deserialize_with = "de_string_to_date")]
+    #[serde(deserialize_with = "de_string_to_date", serialize_with = "se_date_to_string")]
     pub date: Date<Utc>,
     #[serde(rename = "chainSize")]
     #[serde_as(as = "DisplayFromStr")]
     pub size: u64,
-    #[serde(deserialize_with = "de_string_to_client_type")]
     pub client_type: ClientType,
-    #[serde(deserialize_with = "de_string_to_sync_mode")]
     pub sync_mode: SyncMode,
 }
 
+#[allow(deprecated)]
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct NodeStats {
     #[serde(rename = "UTCDate")]
-    #[serde(deserialize_with = "de_string_to_date")]
+    #[serde(deserialize_with = "de_string_to_date", serialize_with = "se_date_to_string")]
     pub date: Date<Utc>,
     #[serde(rename = "TotalNodeCount")]
     #[serde_as(as = "DisplayFromStr")]
@@ -139,7 +163,7 @@ pub struct NodeStats {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub struct Price {
     #[serde_as(as = "DisplayFromStr")]
@@ -153,18 +177,19 @@ pub struct Price {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct TotalSupply {
-    #[serde_as(as = "DisplayFromStr")]
-    pub eth_supply: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub eth_supply: U256,
     #[serde(rename = "Eth2Staking")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub eth_staking: u128,
-    #[serde_as(as = "DisplayFromStr")]
-    pub burnt_fees: u128,
+    #[serde_as(as = "DecimalU256")]
+    pub eth_staking: U256,
+    #[serde_as(as = "DecimalU256")]
+    pub burnt_fees: U256,
 }
 
+#[allow(deprecated)]
 fn de_string_to_date<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<Date<Utc>, D::Error> {
     let str_val = String::deserialize(deserializer)?;
     NaiveDate::parse_from_str(&str_val, "%Y-%m-%d")
@@ -172,24 +197,14 @@ fn de_string_to_date<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::R
         .map_err(Error::custom)
 }
 
-fn de_string_to_client_type<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<ClientType, D::Error> {
-    match String::deserialize(deserializer)?.as_str() {
-        "Geth" => Ok(ClientType::GoEthereum),
-        "Parity" => Ok(ClientType::Parity),
-        other => Err(Error::custom(format!("could not match {other} to a client type"))),
-    }
-}
-
-fn de_string_to_sync_mode<'a, D: Deserializer<'a>>(deserializer: D) -> std::result::Result<SyncMode, D::Error> {
-    match String::deserialize(deserializer)?.as_str() {
-        "Default" => Ok(SyncMode::Default),
-        "Archive" => Ok(SyncMode::Archive),
-        other => Err(Error::custom(format!("could not match {other} to a sync mode"))),
-    }
+#[allow(deprecated)]
+fn se_date_to_string<S: Serializer>(value: &Date<Utc>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(&value.format("%Y-%m-%d"))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum ClientType {
+    #[serde(rename = "Geth")]
     GoEthereum,
     Parity,
 }
@@ -203,7 +218,7 @@ impl ClientType {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum SyncMode {
     Default,
     Archive,
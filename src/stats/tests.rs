@@ -1,19 +1,20 @@
 use super::Client;
 use crate::stats::Stats;
 use crate::stats::{ClientType, Sort, SyncMode};
-use crate::{Address, BlockNumber};
-use chrono::{Duration, NaiveDate, Utc};
+use crate::tests::round_trip;
+use crate::{Address, BlockNumber, U256};
+use chrono::{NaiveDate, Utc};
 use once_cell::sync::Lazy;
 use std::str::FromStr;
 
 const API_KEY: &str = "";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn chain_size() -> Result<(), crate::APIError> {
-    let start_date = NaiveDate::from_ymd(2019, 02, 01);
-    let end_date = NaiveDate::from_ymd(2019, 02, 28);
+    let start_date = NaiveDate::from_ymd_opt(2019, 2, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2019, 2, 28).unwrap();
     let chain_size = CLIENT
         .chain_size(start_date, end_date, ClientType::GoEthereum, SyncMode::Default, Sort::Ascending)
         .await?;
@@ -31,10 +32,11 @@ async fn chain_size() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn last_price() -> Result<(), crate::APIError> {
     let prices = CLIENT.last_price().await?;
-    assert_ne!(0f32, prices.ethbtc);
-    assert_eq!(prices.ethbtc_timestamp.date(), Utc::now().date());
-    assert_ne!(0f32, prices.ethusd);
-    assert_eq!(prices.ethusd_timestamp.date(), Utc::now().date());
+    assert!(prices.ethbtc > 0f32);
+    assert!(prices.ethusd > 0f32);
+    // Both prices are from the same update
+    assert!((prices.ethbtc_timestamp - prices.ethusd_timestamp).num_hours().abs() < 1);
+    assert!(prices.ethusd_timestamp <= Utc::now());
 
     println!("{:#?}", prices);
     Ok(())
@@ -43,7 +45,9 @@ async fn last_price() -> Result<(), crate::APIError> {
 #[tokio::test]
 async fn nodes() -> Result<(), crate::APIError> {
     let stats = CLIENT.nodes().await?;
-    assert_eq!(Utc::now().date() - Duration::days(1), stats.date);
+    // Node stats start after the genesis block
+    assert!(stats.date.naive_utc() > NaiveDate::from_ymd_opt(2015, 7, 30).unwrap());
+    assert!(stats.date.naive_utc() <= Utc::now().date_naive());
     assert_ne!(0, stats.total_nodes);
     println!("{:#?}", stats);
     Ok(())
@@ -53,23 +57,56 @@ async fn nodes() -> Result<(), crate::APIError> {
 async fn token_supply() -> Result<(), crate::APIError> {
     let address = Address::from_str("0x57d90b64a1a57749b0f932f1a3395792e12e7055").expect("could not parse address");
     let supply = CLIENT.token_supply(&address).await?;
-    assert_eq!(21265524714464, supply);
+    assert_eq!(U256::from(21265524714464u64), supply);
     Ok(())
 }
 
 #[tokio::test]
 async fn total_supply() -> Result<(), crate::APIError> {
     let supply = CLIENT.total_supply().await?;
-    assert_ne!(0, supply);
+    assert!(!supply.is_zero());
     Ok(())
 }
 
 #[tokio::test]
 async fn total_supply_stats() -> Result<(), crate::APIError> {
     let stats = CLIENT.total_supply_stats().await?;
-    assert_ne!(0, stats.eth_supply);
-    assert_ne!(0, stats.eth_staking);
-    assert_ne!(0, stats.burnt_fees);
+    assert!(!stats.eth_supply.is_zero());
+    assert!(!stats.eth_staking.is_zero());
+    assert!(!stats.burnt_fees.is_zero());
     println!("{:#?}", stats);
     Ok(())
 }
+
+#[tokio::test]
+async fn chain_size_nothing_found() -> Result<(), crate::APIError> {
+    // Before the genesis block
+    let start_date = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2015, 1, 31).unwrap();
+    let chain_size = CLIENT
+        .chain_size(start_date, end_date, ClientType::GoEthereum, SyncMode::Default, Sort::Ascending)
+        .await?;
+    assert!(chain_size.is_empty());
+    Ok(())
+}
+
+#[test]
+fn round_trips() {
+    let chain_size = round_trip::<super::ChainSize>(
+        r#"{"blockNumber":"7156164","chainTimeStamp":"2019-02-01","chainSize":"184726421279","clientType":"Geth","syncMode":"Default"}"#,
+    );
+    assert_eq!(ClientType::GoEthereum, chain_size.client_type);
+    assert_eq!(ClientType::Parity, round_trip::<ClientType>(r#""Parity""#));
+    assert_eq!(SyncMode::Archive, round_trip::<SyncMode>(r#""Archive""#));
+    assert_eq!(
+        r#""Geth""#,
+        serde_json::to_string(&ClientType::GoEthereum).expect("could not serialize client type")
+    );
+    round_trip::<super::NodeStats>(r#"{"UTCDate":"2019-02-01","TotalNodeCount":"7846"}"#);
+    round_trip::<super::Price>(
+        r#"{"ethbtc":"0.06116","ethbtc_timestamp":"1624961308","ethusd":"2149.18","ethusd_timestamp":"1624961308"}"#,
+    );
+    round_trip::<super::TotalSupply>(
+        r#"{"EthSupply":"122373866217800000000000000","Eth2Staking":"1157529105115885000000000","BurntFees":"3102505506455601519229842"}"#,
+    );
+}
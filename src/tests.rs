@@ -0,0 +1,714 @@
+use super::{Client, Network};
+use crate::accounts::Accounts;
+use crate::cache::MemoryCache;
+use crate::cassette::Cassette;
+use crate::contracts::Contracts;
+use crate::proxy::Proxy;
+use crate::rate_limit::RateLimit;
+use crate::retry::RetryPolicy;
+use crate::transport::{HttpTransport, Request, Response, Transport};
+use crate::{APIError, Address, BlockNumber, U256};
+use async_trait::async_trait;
+use proptest::prelude::*;
+use reqwest::header::{HeaderName, HeaderValue};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+const ADDRESS: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
+
+/// Returns a client for the tests of each module, which replays responses from the fixtures directory unless the
+/// 'ETHERSCAN_CASSETTE' environment variable is set to 'record', to record responses from the live API to the fixtures
+/// directory, or 'live', to use the live API without recording.
+///
+/// When recording or live, the 'ETHERSCAN_API_KEY' environment variable is used as the API key (if set).
+pub(crate) fn client(api_key: &str) -> Client {
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+    let api_key = std::env::var("ETHERSCAN_API_KEY").unwrap_or_else(|_| api_key.to_string());
+    let builder = Client::builder(api_key);
+    let builder = match std::env::var("ETHERSCAN_CASSETTE").as_deref() {
+        Ok("record") => builder.transport(Cassette::record(FIXTURES, HttpTransport::default())),
+        Ok("live") => builder,
+        _ => builder.transport(Cassette::replay(FIXTURES)),
+    };
+    builder.build().expect("could not build client")
+}
+
+/// Returns whether the clients of the tests of each module replay recorded responses, rather than calling the live API.
+pub(crate) fn replaying() -> bool {
+    !matches!(std::env::var("ETHERSCAN_CASSETTE").as_deref(), Ok("record" | "live"))
+}
+
+/// Waits between requests to the live API, to stay within its rate limit, unless replaying recorded responses.
+pub(crate) async fn rate_limit_pause() {
+    if !replaying() {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+/// An in-memory transport, which answers every request with the given body and records the requests it was sent.
+#[derive(Clone)]
+pub(crate) struct FakeTransport {
+    bodies: Vec<String>,
+    pub(crate) requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl FakeTransport {
+    pub(crate) fn new(body: impl Into<String>) -> FakeTransport {
+        FakeTransport::responses(vec![body.into()])
+    }
+
+    /// Creates a transport which answers requests with the given bodies in turn, repeating the last once exhausted.
+    pub(crate) fn responses(bodies: Vec<String>) -> FakeTransport {
+        FakeTransport {
+            bodies,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Returns a client which sends its requests to the transport.
+    pub(crate) fn client(&self) -> Client {
+        Client::builder("KEY")
+            .transport(self.clone())
+            .build()
+            .expect("could not build client")
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    async fn get(&self, request: &Request) -> crate::Result<Response> {
+        let mut requests = self.requests.lock().expect("lock poisoned");
+        requests.push(request.clone());
+        let body = &self.bodies[(requests.len() - 1).min(self.bodies.len() - 1)];
+        Ok(Response {
+            status: 200,
+            body: body.clone(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Parses a captured result, asserting that it serializes back to the same JSON and parses to an equal value again.
+pub(crate) fn round_trip<T: serde::de::DeserializeOwned + serde::Serialize + PartialEq + std::fmt::Debug>(result: &str) -> T {
+    let expected: serde_json::Value = serde_json::from_str(result).expect("invalid JSON");
+    let value: T = serde_json::from_str(result).expect("could not parse result");
+    let serialized = serde_json::to_value(&value).expect("could not serialize result");
+    assert_eq!(expected, serialized);
+    assert_eq!(
+        value,
+        serde_json::from_value(serialized).expect("could not parse serialized result")
+    );
+    value
+}
+
+/// Returns a strategy for arbitrary JSON values, favouring the field names and values of API responses.
+pub(crate) fn json() -> impl Strategy<Value = serde_json::Value> {
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        any::<f64>().prop_map(serde_json::Value::from),
+        any::<String>().prop_map(serde_json::Value::from),
+        prop::sample::select(vec![
+            "0",
+            "1",
+            "2",
+            "OK",
+            "NOTOK",
+            "No transactions found",
+            "0x",
+            "0x1",
+            "0xffffffffffffffffffff"
+        ])
+        .prop_map(serde_json::Value::from),
+    ];
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(serde_json::Value::from),
+            prop::collection::vec((field(), inner), 0..8).prop_map(|fields| serde_json::Value::Object(fields.into_iter().collect())),
+        ]
+    })
+}
+
+/// Returns a strategy for field names, favouring those of API responses.
+fn field() -> impl Strategy<Value = String> {
+    prop_oneof![
+        prop::sample::select(vec![
+            "status", "message", "result", "id", "jsonrpc", "error", "code", "number", "hash"
+        ])
+        .prop_map(String::from),
+        any::<String>(),
+    ]
+}
+
+/// Returns a strategy for arbitrary response bodies, which are JSON objects with any of the fields of explorer and
+/// JSON-RPC responses (including duplicates).
+pub(crate) fn response() -> impl Strategy<Value = String> {
+    prop::collection::vec((field(), json()), 0..6).prop_map(|fields| {
+        let fields = fields
+            .iter()
+            .map(|(key, value)| format!("{}:{}", serde_json::Value::from(key.as_str()), value))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", fields.join(","))
+    })
+}
+
+/// Starts a local stand-in for the Etherscan API, which answers every request with the given body.
+///
+/// Returns the URI of the stand-in API.
+pub(crate) async fn serve(body: &'static str) -> String {
+    serve_with_requests(body).await.0
+}
+
+/// Starts a local stand-in for the Etherscan API, which answers every request with the given body.
+///
+/// Returns the URI of the stand-in API, along with a receiver of the raw requests it was sent.
+pub(crate) async fn serve_with_requests(body: &'static str) -> (String, UnboundedReceiver<String>) {
+    serve_responses(vec![(200, body)]).await
+}
+
+/// Starts a local stand-in for the Etherscan API, which answers requests with the given status codes and bodies in turn,
+/// repeating the last once exhausted. Rate limited (429) responses ask to be retried after two seconds.
+///
+/// Returns the URI of the stand-in API, along with a receiver of the raw requests it was sent.
+pub(crate) async fn serve_responses(responses: Vec<(u16, &'static str)>) -> (String, UnboundedReceiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("could not bind listener");
+    let address = listener.local_addr().expect("could not get listener address");
+    let (sender, receiver) = unbounded_channel();
+    tokio::spawn(async move {
+        let mut responses = responses.into_iter().peekable();
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buffer = [0; 4096];
+            let read = stream.read(&mut buffer).await.unwrap_or(0);
+            let _ = sender.send(String::from_utf8_lossy(&buffer[..read]).to_string());
+            let (status, body) = match responses.next() {
+                Some(response) if responses.peek().is_none() => {
+                    responses = vec![response].into_iter().peekable();
+                    response
+                }
+                Some(response) => response,
+                None => break,
+            };
+            let retry_after = if status == 429 { "Retry-After: 2\r\n" } else { "" };
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{retry_after}Connection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    (format!("http://{address}/api"), receiver)
+}
+
+#[test]
+fn network_uri() {
+    assert_eq!("https://api.etherscan.io/v2/api", Network::Mainnet.uri());
+    assert_eq!("https://api.etherscan.io/v2/api", Network::Polygon.uri());
+    assert_eq!(
+        "http://localhost:8080/api",
+        Network::Custom("http://localhost:8080/api".to_string()).uri()
+    );
+    assert_eq!(Network::Mainnet, Network::default());
+}
+
+#[test]
+fn network_chain_id() {
+    assert_eq!(Some(1), Network::Mainnet.chain_id());
+    assert_eq!(Some(42161), Network::Arbitrum.chain_id());
+    assert_eq!(Some(10), Network::Chain(10).chain_id());
+    assert_eq!(None, Network::Custom("http://localhost:8080/api".to_string()).chain_id());
+}
+
+#[test]
+fn request_chain_id() {
+    let client = Client::new_with_network("KEY", Network::Polygon);
+    let request = client.request("KEY", &[("module", "account"), ("action", "balance"), ("tag", "latest")]);
+    assert_eq!("https://api.etherscan.io/v2/api", request.uri);
+    assert_eq!("account", request.module);
+    assert_eq!("balance", request.action);
+    assert_eq!(
+        vec![
+            ("module", "account"),
+            ("action", "balance"),
+            ("apikey", "KEY"),
+            ("chainid", "137"),
+            ("tag", "latest")
+        ],
+        request.query()
+    );
+
+    let client = client.for_network(Network::Custom("http://localhost:8080/api".to_string()));
+    let request = client.request("KEY", &[("module", "account"), ("action", "balance")]);
+    assert_eq!(
+        vec![("module", "account"), ("action", "balance"), ("apikey", "KEY")],
+        request.query()
+    );
+}
+
+#[tokio::test]
+async fn custom_network() -> Result<(), crate::APIError> {
+    let uri = serve(r#"{"status":"1","message":"OK","result":"40891626854930000000000"}"#).await;
+    let client = Client::new_with_network("", Network::Custom(uri));
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(U256::from(40891626854930000000000u128), client.balance(&address, None).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn custom_network_json_rpc() -> Result<(), crate::APIError> {
+    let uri = serve(r#"{"jsonrpc":"2.0","id":83,"result":"0xc36b29"}"#).await;
+    let client = Client::new_with_network("", Network::Custom(uri));
+    assert_eq!(BlockNumber::from(12806953), client.block_number().await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn across_networks() {
+    let first = Network::Custom(serve(r#"{"status":"1","message":"OK","result":"1"}"#).await);
+    let second = Network::Custom(serve(r#"{"status":"1","message":"OK","result":"2"}"#).await);
+    let client = Client::new("");
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let balances = client
+        .across_networks(&[first.clone(), second.clone()], |client| async move {
+            client.balance(&address, None).await
+        })
+        .await;
+    assert_eq!(2, balances.len());
+    assert_eq!(U256::from(1), *balances[&first].as_ref().expect("expected balance"));
+    assert_eq!(U256::from(2), *balances[&second].as_ref().expect("expected balance"));
+}
+
+#[tokio::test]
+async fn builder_headers() -> Result<(), crate::APIError> {
+    let (uri, mut requests) = serve_with_requests(r#"{"status":"1","message":"OK","result":"0"}"#).await;
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .user_agent(HeaderValue::from_static("etherscan-tests/1.0"))
+        .header(HeaderName::from_static("x-team"), HeaderValue::from_static("data"))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    client.balance(&address, None).await?;
+    let request = requests.recv().await.expect("expected request").to_lowercase();
+    assert!(request.contains("user-agent: etherscan-tests/1.0"));
+    assert!(request.contains("x-team: data"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn builder_http_client() -> Result<(), crate::APIError> {
+    let uri = serve(r#"{"status":"1","message":"OK","result":"1"}"#).await;
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .http_client(reqwest::Client::new())
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(U256::from(1), client.balance(&address, None).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn builder_timeout() -> Result<(), crate::APIError> {
+    // A listener which never responds
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("could not bind listener");
+    let uri = format!("http://{}/api", listener.local_addr().expect("could not get listener address"));
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .timeout(Duration::from_millis(100))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    match client.balance(&address, None).await {
+        Err(APIError::TransportError { source, context }) => {
+            assert!(source.is_timeout());
+            assert_eq!("balance", context.request.action);
+            assert_eq!(0, context.status);
+        }
+        other => panic!("expected timeout, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limit() -> Result<(), crate::APIError> {
+    let uri = serve(r#"{"jsonrpc":"2.0","id":83,"result":"0xc36b29"}"#).await;
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .rate_limit(RateLimit::Custom {
+            requests: 1,
+            period: Duration::from_millis(200),
+        })
+        .build()?;
+    let start = Instant::now();
+    for _ in 0..3 {
+        client.block_number().await?;
+    }
+    assert!(start.elapsed() >= Duration::from_millis(400));
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry() -> Result<(), crate::APIError> {
+    let (uri, mut requests) = serve_responses(vec![
+        (200, r#"{"status":"0","message":"NOTOK","result":"Max rate limit reached"}"#),
+        (503, ""),
+        (200, r#"{"status":"1","message":"OK","result":"1"}"#),
+    ])
+    .await;
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .retry(RetryPolicy::new(3).backoff(Duration::from_millis(10), Duration::from_millis(10)))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(U256::from(1), client.balance(&address, None).await?);
+    requests.close();
+    let mut attempts = 0;
+    while requests.recv().await.is_some() {
+        attempts += 1;
+    }
+    assert_eq!(3, attempts);
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_exhausted() -> Result<(), crate::APIError> {
+    let uri = serve(r#"{"status":"0","message":"NOTOK","result":"Max rate limit reached"}"#).await;
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .retry(RetryPolicy::new(2).backoff(Duration::from_millis(10), Duration::from_millis(10)))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let result = client.balance(&address, None).await;
+    assert!(matches!(result, Err(APIError::RateLimitReached { .. })));
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_not_retryable() -> Result<(), crate::APIError> {
+    let (uri, mut requests) = serve_responses(vec![
+        (200, r#"{"status":"0","message":"NOTOK","result":"Invalid Address format"}"#),
+        (200, r#"{"status":"1","message":"OK","result":"1"}"#),
+    ])
+    .await;
+    let client = Client::builder("")
+        .network(Network::Custom(uri))
+        .retry(RetryPolicy::new(3).backoff(Duration::from_millis(10), Duration::from_millis(10)))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let result = client.balance(&address, None).await;
+    assert!(matches!(result, Err(APIError::InvalidAddress { .. })));
+    requests.close();
+    let mut attempts = 0;
+    while requests.recv().await.is_some() {
+        attempts += 1;
+    }
+    assert_eq!(1, attempts);
+    Ok(())
+}
+
+#[tokio::test]
+async fn key_pool() -> Result<(), crate::APIError> {
+    let (uri, mut requests) = serve_responses(vec![
+        (200, r#"{"status":"0","message":"NOTOK","result":"Max rate limit reached"}"#),
+        (200, r#"{"status":"1","message":"OK","result":"1"}"#),
+    ])
+    .await;
+    let client = Client::builder("FIRSTKEY")
+        .api_key("SECONDKEY")
+        .network(Network::Custom(uri))
+        .retry(RetryPolicy::new(2).backoff(Duration::from_millis(10), Duration::from_millis(10)))
+        .build()?;
+    assert_eq!("FIRSTKEY", client.api_key());
+    assert_eq!(vec!["FIRSTKEY".to_string(), "SECONDKEY".to_string()], client.api_keys());
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(U256::from(1), client.balance(&address, None).await?);
+    assert!(requests.recv().await.expect("expected request").contains("apikey=FIRSTKEY"));
+    assert!(requests.recv().await.expect("expected request").contains("apikey=SECONDKEY"));
+
+    let usage = client.key_usage();
+    assert_eq!(1, usage[0].rate_limited);
+    assert!(usage[0].suspended);
+    assert_eq!(1, usage[1].requests);
+    assert!(!usage[1].suspended);
+    // The first key is moved aside, so the next request is sent with the second
+    assert_eq!("SECONDKEY", client.api_key());
+
+    // The first key remains moved aside
+    client.balance(&address, None).await?;
+    assert!(requests.recv().await.expect("expected request").contains("apikey=SECONDKEY"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transport() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"12"}"#);
+    let client = transport.client();
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(U256::from(12), client.balance(&address, None).await?);
+    let requests = transport.requests.lock().expect("lock poisoned");
+    assert_eq!(1, requests.len());
+    assert_eq!("account", requests[0].module);
+    assert_eq!("balance", requests[0].action);
+    assert!(requests[0].parameters.contains(&("address".to_string(), ADDRESS.to_string())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transport_server_error() {
+    struct Unavailable;
+
+    #[async_trait]
+    impl Transport for Unavailable {
+        async fn get(&self, _: &Request) -> crate::Result<Response> {
+            Ok(Response {
+                status: 503,
+                ..Default::default()
+            })
+        }
+    }
+
+    let client = Client::builder("").transport(Unavailable).build().expect("could not build client");
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert!(matches!(
+        client.balance(&address, None).await,
+        Err(APIError::ServerError { status: 503, .. })
+    ));
+}
+
+#[tokio::test(start_paused = true)]
+async fn cache_immutable() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"[]"}"#);
+    let client = Client::builder("KEY")
+        .transport(transport.clone())
+        .cache(MemoryCache::new(), Duration::from_secs(10))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+
+    // Immutable data is cached forever
+    client.get_abi(&address).await?;
+    client.get_abi(&address).await?;
+    tokio::time::advance(Duration::from_secs(3600)).await;
+    client.get_abi(&address).await?;
+    assert_eq!(1, transport.requests.lock().expect("lock poisoned").len());
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn cache_ttl() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"12"}"#);
+    let client = Client::builder("KEY")
+        .transport(transport.clone())
+        .cache(MemoryCache::new(), Duration::from_secs(10))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert_eq!(U256::from(12), client.balance(&address, None).await?);
+    assert_eq!(U256::from(12), client.balance(&address, None).await?);
+    assert_eq!(1, transport.requests.lock().expect("lock poisoned").len());
+    tokio::time::advance(Duration::from_secs(11)).await;
+    assert_eq!(U256::from(12), client.balance(&address, None).await?);
+    assert_eq!(2, transport.requests.lock().expect("lock poisoned").len());
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn cache_finality() -> Result<(), crate::APIError> {
+    /// Answers the latest block number and the transaction count of a block, counting the requests for the latter.
+    #[derive(Clone, Default)]
+    struct Chain {
+        counts: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl Transport for Chain {
+        async fn get(&self, request: &Request) -> crate::Result<Response> {
+            let result = match request.action.as_str() {
+                "eth_blockNumber" => "0x1000",
+                _ => {
+                    *self.counts.lock().expect("lock poisoned") += 1;
+                    "0x2"
+                }
+            };
+            Ok(Response {
+                status: 200,
+                body: format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{result}"}}"#),
+                ..Default::default()
+            })
+        }
+    }
+
+    let transport = Chain::default();
+    let client = Client::builder("KEY")
+        .transport(transport.clone())
+        .cache(MemoryCache::new(), Duration::from_secs(10))
+        .build()?;
+    let block_number = BlockNumber::from(0x10);
+    let counts = || *transport.counts.lock().expect("lock poisoned");
+
+    // Until the latest block number is observed, data from any block expires after the TTL
+    client.block_transactions(&block_number).await?;
+    client.block_transactions(&block_number).await?;
+    assert_eq!(1, counts());
+    tokio::time::advance(Duration::from_secs(11)).await;
+    client.block_transactions(&block_number).await?;
+    assert_eq!(2, counts());
+
+    // Once observed, data from blocks far enough behind it is cached forever
+    client.block_number().await?;
+    tokio::time::advance(Duration::from_secs(11)).await;
+    client.block_transactions(&block_number).await?;
+    assert_eq!(3, counts());
+    tokio::time::advance(Duration::from_secs(3600)).await;
+    client.block_transactions(&block_number).await?;
+    assert_eq!(3, counts());
+    Ok(())
+}
+
+#[tokio::test]
+async fn cache_failures() -> Result<(), crate::APIError> {
+    let transport = FakeTransport::new(r#"{"status":"0","message":"NOTOK","result":"Contract source code not verified"}"#);
+    let client = Client::builder("KEY")
+        .transport(transport.clone())
+        .cache(MemoryCache::new(), Duration::from_secs(10))
+        .build()?;
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    assert!(client.get_abi(&address).await.is_err());
+    assert!(client.get_abi(&address).await.is_err());
+    assert_eq!(2, transport.requests.lock().expect("lock poisoned").len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn api_error() {
+    let transport = FakeTransport::new(r#"{"status":"0","message":"NOTOK","result":{"reason":"unknown"}}"#);
+    let client = Client::builder("SECRET")
+        .transport(transport)
+        .build()
+        .expect("could not build client");
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    match client.balance(&address, None).await {
+        Err(APIError::ApiError { message, result, context }) => {
+            assert_eq!("NOTOK", message);
+            assert_eq!(serde_json::json!({"reason": "unknown"}), result);
+            assert_eq!("account", context.request.module);
+            assert_eq!("balance", context.request.action);
+            assert!(context.request.parameters.contains(&("apikey".to_string(), "REDACTED".to_string())));
+            assert_eq!(200, context.status);
+            assert!(context.body.contains("unknown"));
+        }
+        result => panic!("expected API error, got {:?}", result),
+    }
+}
+
+#[tokio::test]
+async fn api_error_known() {
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let client = FakeTransport::new(r#"{"status":"0","message":"NOTOK","result":"Max calls per sec rate limit reached (5/sec)"}"#).client();
+    assert!(matches!(
+        client.balance(&address, None).await,
+        Err(APIError::RateLimitReached { .. })
+    ));
+    let client = FakeTransport::new(r#"{"status":"0","message":"NOTOK","result":"Invalid API Key"}"#).client();
+    assert!(matches!(client.balance(&address, None).await, Err(APIError::InvalidAPIKey { .. })));
+    let client = FakeTransport::new(r#"{"status":"0","message":"NOTOK","result":"Invalid API Key"}"#).client();
+    assert!(matches!(client.block_number().await, Err(APIError::InvalidAPIKey { .. })));
+}
+
+#[tokio::test]
+async fn rpc_error() {
+    let transport = FakeTransport::new(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"execution reverted"}}"#);
+    match transport.client().block_number().await {
+        Err(e @ APIError::RPCError { .. }) => {
+            assert!(matches!(e, APIError::RPCError { code: -32000, .. }));
+            assert_eq!("eth_blockNumber", e.context().expect("expected context").request.action);
+        }
+        result => panic!("expected RPC error, got {:?}", result),
+    }
+}
+
+#[tokio::test]
+async fn deserialization_error() {
+    let transport = FakeTransport::new(r#"{"status":"1","message":"OK","result":"not an abi"}"#);
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    match transport.client().get_abi(&address).await {
+        Err(APIError::DeserializationError { context, .. }) => assert!(context.body.contains("not an abi")),
+        result => panic!("expected deserialization error, got {:?}", result),
+    }
+}
+
+#[tokio::test]
+async fn amount() -> Result<(), APIError> {
+    // Larger than u128
+    let wei = "1157920892373161954235709850086879078532699846656405640394575840079131296399";
+    let transport = FakeTransport::new(format!(r#"{{"status":"1","message":"OK","result":"{wei}"}}"#));
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let balance = transport.client().balance(&address, None).await?;
+    assert_eq!(U256::from_dec_str(wei).expect("could not parse amount"), balance);
+    Ok(())
+}
+
+#[tokio::test]
+async fn amount_invalid() {
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    for result in ["", "abc", "-1", "1.5", "0x10"] {
+        let transport = FakeTransport::new(format!(r#"{{"status":"1","message":"OK","result":"{result}"}}"#));
+        let balance = transport.client().balance(&address, None).await;
+        assert!(
+            matches!(balance, Err(APIError::DeserializationError { .. })),
+            "expected deserialization error for '{result}', got {:?}",
+            balance
+        );
+    }
+}
+
+#[tokio::test]
+async fn too_many_requests() {
+    let (uri, _) = serve_responses(vec![(429, "Too Many Requests")]).await;
+    let client = Client::new_with_network("", Network::Custom(uri));
+    let address = Address::from_str(ADDRESS).expect("could not parse {ADDRESS} as address");
+    let error = client.balance(&address, None).await.expect_err("expected rate limit");
+    assert!(error.is_rate_limit());
+    assert!(error.is_retryable());
+    assert_eq!(Some(Duration::from_secs(2)), error.retry_delay());
+}
+
+#[test]
+fn error_classification() {
+    let context = || Box::<crate::RequestContext>::default();
+    let invalid_address = APIError::InvalidAddress { context: context() };
+    assert!(!invalid_address.is_retryable() && !invalid_address.is_rate_limit() && !invalid_address.is_not_found());
+
+    let not_verified = APIError::ContractNotVerified { context: context() };
+    assert!(!not_verified.is_retryable() && not_verified.is_not_found());
+
+    let server_error = APIError::ServerError {
+        status: 502,
+        context: context(),
+    };
+    assert!(server_error.is_retryable() && !server_error.is_rate_limit());
+
+    let rate_limit = APIError::RateLimitReached {
+        message: "Max rate limit reached".to_string(),
+        context: context(),
+    };
+    assert!(rate_limit.is_retryable() && rate_limit.is_rate_limit());
+    assert_eq!(None, rate_limit.retry_delay());
+
+    let nothing_found = APIError::ApiError {
+        message: "No records found".to_string(),
+        result: serde_json::Value::Null,
+        context: context(),
+    };
+    assert!(nothing_found.is_not_found() && !nothing_found.is_retryable());
+
+    let missing = APIError::DeserializationError {
+        message: "expected value".to_string(),
+        context: Box::new(crate::RequestContext {
+            status: 404,
+            ..Default::default()
+        }),
+    };
+    assert!(missing.is_not_found());
+}
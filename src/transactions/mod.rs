@@ -2,7 +2,7 @@ use super::BoolFromStr;
 use super::Result;
 use crate::{Client, TransactionHash, TypeExtensions, ACTION, MODULE};
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 #[cfg(test)]
@@ -54,7 +54,7 @@ impl Transactions for Client {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecutionStatus {
     #[serde_as(as = "BoolFromStr")]
@@ -1,4 +1,5 @@
 use super::Client;
+use crate::tests::round_trip;
 use crate::transactions::Transactions;
 use crate::TransactionHash;
 use once_cell::sync::Lazy;
@@ -6,7 +7,7 @@ use std::str::FromStr;
 
 const API_KEY: &str = "";
 
-static CLIENT: Lazy<Client> = Lazy::new(|| Client::new(API_KEY));
+static CLIENT: Lazy<Client> = Lazy::new(|| crate::tests::client(API_KEY));
 
 #[tokio::test]
 async fn execution_status() -> Result<(), crate::APIError> {
@@ -26,3 +27,9 @@ async fn receipt_status() -> Result<(), crate::APIError> {
     assert!(status);
     Ok(())
 }
+
+#[test]
+fn round_trips() {
+    let status = round_trip::<super::ExecutionStatus>(r#"{"isError":"1","errDescription":"Bad jump destination"}"#);
+    assert!(status.is_error);
+}
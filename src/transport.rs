@@ -0,0 +1,191 @@
+use crate::Result;
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub(crate) const API_KEY: &str = "apikey";
+const REDACTED: &str = "REDACTED";
+
+/// A transport which sends requests to the explorer API, such as over HTTP.
+///
+/// The default transport uses `reqwest`, but others can be provided to a client via its builder, such as in-memory
+/// fakes for tests, recorders or other HTTP stacks.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Transport: Send + Sync {
+    /// Sends a request, returning the raw response.
+    ///
+    /// # Arguments
+    ///
+    /// * 'request' - The request to send
+    async fn get(&self, request: &Request) -> Result<Response>;
+}
+
+/// A request to the explorer API.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub struct Request {
+    /// The URI of the explorer API
+    pub uri: String,
+    /// The API module, such as 'account'
+    pub module: String,
+    /// The API action, such as 'balance'
+    pub action: String,
+    /// The remaining query parameters, including the API key and chain id (if any)
+    pub parameters: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Returns the query parameters of the request, starting with the module and action.
+    pub fn query(&self) -> Vec<(&str, &str)> {
+        [(crate::MODULE, self.module.as_str()), (crate::ACTION, self.action.as_str())]
+            .into_iter()
+            .chain(self.parameters.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .collect()
+    }
+
+    /// Returns a copy of the request with the API key redacted, such as for logging or storing.
+    pub fn redacted(&self) -> Request {
+        let mut request = self.clone();
+        for (key, value) in request.parameters.iter_mut() {
+            if key == API_KEY {
+                *value = REDACTED.to_string();
+            }
+        }
+        request
+    }
+
+    /// Returns a key identifying the request, from its URI, module, action and sorted parameters (excluding the API key),
+    /// so the same request matches regardless of the key it is sent with or the order its parameters were added in.
+    ///
+    /// The parameters are included in full rather than hashed, so distinct requests never share a key.
+    pub(crate) fn key(&self) -> String {
+        let mut parameters: Vec<_> = self.parameters.iter().filter(|(key, _)| key != API_KEY).collect();
+        parameters.sort();
+        format!(
+            "{}|{}|{}|{}",
+            self.uri,
+            self.module,
+            self.action,
+            serde_json::to_string(&parameters).unwrap_or_default()
+        )
+    }
+
+    /// Returns a hash of the [key](Request::key) of the request, which is stable across platforms and compiler versions.
+    pub fn fingerprint(&self) -> u64 {
+        // FNV-1a
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in self.key().bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
+}
+
+/// A raw response from the explorer API.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Response {
+    /// The HTTP status code
+    pub status: u16,
+    /// The response body
+    pub body: String,
+    /// How long to wait before retrying, from the 'Retry-After' header (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<Duration>,
+}
+
+/// The default transport, which sends requests over HTTP using `reqwest`.
+#[derive(Clone, Debug, Default)]
+pub struct HttpTransport {
+    client: reqwest::Client,
+}
+
+impl HttpTransport {
+    /// Creates a new transport.
+    ///
+    /// # Arguments
+    ///
+    /// * 'client' - The HTTP client requests are sent with
+    pub fn new(client: reqwest::Client) -> HttpTransport {
+        HttpTransport { client }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Transport for HttpTransport {
+    async fn get(&self, request: &Request) -> Result<Response> {
+        let response = self.client.get(&request.uri).query(&request.query()).send().await?;
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(retry_after);
+        Ok(Response {
+            status: response.status().as_u16(),
+            body: response.text().await?,
+            retry_after,
+        })
+    }
+}
+
+/// Parses the value of a 'Retry-After' header, which is either a number of seconds or an HTTP date.
+fn retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retry_after, Request};
+    use std::time::Duration;
+
+    fn request(uri: &str, parameters: &[(&str, &str)]) -> Request {
+        Request {
+            uri: uri.to_string(),
+            module: "account".to_string(),
+            action: "balance".to_string(),
+            parameters: parameters.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn fingerprint() {
+        let uri = "https://api.etherscan.io/v2/api";
+        let first = request(uri, &[("apikey", "KEY1"), ("chainid", "1"), ("address", "0x1")]);
+        let reordered = request(uri, &[("address", "0x1"), ("chainid", "1"), ("apikey", "KEY2")]);
+        assert_eq!(first.fingerprint(), reordered.fingerprint());
+
+        // Requests to other networks or servers, or for other actions, do not match
+        let chain = request(uri, &[("apikey", "KEY1"), ("chainid", "10"), ("address", "0x1")]);
+        let custom = request(
+            "http://localhost:8080/api",
+            &[("apikey", "KEY1"), ("chainid", "1"), ("address", "0x1")],
+        );
+        let action = Request {
+            action: "balancemulti".to_string(),
+            ..first.clone()
+        };
+        for other in [chain, custom, action] {
+            assert_ne!(first.fingerprint(), other.fingerprint());
+        }
+    }
+
+    #[test]
+    fn retry_after_seconds() {
+        assert_eq!(Some(Duration::from_secs(30)), retry_after("30"));
+        assert_eq!(None, retry_after("soon"));
+    }
+
+    #[test]
+    fn retry_after_date() {
+        let date = (chrono::Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        let delay = retry_after(&date).expect("expected delay");
+        assert!(delay > Duration::from_secs(100) && delay <= Duration::from_secs(120));
+        assert_eq!(None, retry_after("Wed, 21 Oct 2015 07:28:00 GMT"));
+    }
+}